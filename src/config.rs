@@ -0,0 +1,255 @@
+use std::{io::ErrorKind, ops::RangeInclusive, time::Duration};
+
+use crate::{
+    game::{Budget, Rules, ScoringMode, Theme, WinnableDeals, MAX_STACKS_COUNT},
+    storage,
+};
+
+pub const USAGE: &str = "Usage: consolitaire [options]
+
+Options:
+    --seed <number>      Deal the game identified by this seed
+    --deal <number>      Deal the Microsoft numbered game
+    --board <file>       Lay the cards out as written in the file
+    --draw <1-3>         Cards drawn from the deck at once (default: 3)
+    --passes <number>    Times the deck can be gone through, or 'unlimited'
+    --columns <1-10>     Number of tableau columns (default: 7)
+    --vegas              Use Vegas scoring
+    --cumulative         Use Vegas scoring and keep the bankroll between deals
+    --winnable           Deal only games the solver proves winnable
+    --attempts <number>  Deals tried before settling for one (default: 20)
+    --solve-time <secs>  Time the solver gets for each deal (default: 2)
+    --solve              Print a winning line for the deal instead of playing
+    --print-board        Print the cards as laid out instead of playing
+    --replay <file>      Step through a recorded game instead of playing
+    --theme <name>       Colors: classic, dark, high-contrast, four-color
+                         or monochrome (default: classic)
+    -h, --help           Print this help
+
+Defaults for draw, passes, columns, scoring, cumulative, winnable, attempts,
+solve-time and theme can be set in ~/.consolitaire/config as 'name = value'
+lines.";
+
+const CONFIG_FILE: &str = "config";
+const DRAW_COUNTS: RangeInclusive<usize> = 1..=3;
+const DEFAULT_ATTEMPTS: usize = 20;
+const DEFAULT_SOLVE_SECONDS: u64 = 2;
+
+#[derive(Debug, Default, Clone, Copy)]
+enum Passes {
+    #[default]
+    ByScoring,
+    Unlimited,
+    Limited(usize),
+}
+
+#[derive(Debug)]
+pub struct Config {
+    pub seed: Option<u64>,
+    pub deal: Option<u32>,
+    pub board: Option<String>,
+    pub replay: Option<String>,
+    pub cumulative: bool,
+    pub solve: bool,
+    pub print_board: bool,
+    pub help: bool,
+    pub theme: Theme,
+    /// Whether any rule was given on the command line, rather than taken
+    /// from the config file or the defaults.
+    pub has_rule_options: bool,
+    winnable: bool,
+    attempts: usize,
+    solve_seconds: u64,
+    stacks_count: usize,
+    draw_count: usize,
+    passes: Passes,
+    scoring_mode: ScoringMode,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let rules = Rules::default();
+
+        Self {
+            seed: None,
+            deal: None,
+            board: None,
+            replay: None,
+            cumulative: false,
+            solve: false,
+            print_board: false,
+            help: false,
+            theme: Theme::default(),
+            has_rule_options: false,
+            winnable: false,
+            attempts: DEFAULT_ATTEMPTS,
+            solve_seconds: DEFAULT_SOLVE_SECONDS,
+            stacks_count: rules.stacks_count,
+            draw_count: rules.draw_count,
+            passes: Passes::default(),
+            scoring_mode: rules.scoring_mode,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config file first, so that command-line options override it.
+    pub fn load(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        match storage::read(CONFIG_FILE) {
+            Ok(Some(contents)) => config.apply_file(&contents)?,
+            Ok(None) => {}
+            Err(error) if error.kind() == ErrorKind::NotFound => {}
+            Err(error) => return Err(format!("Can't read the config file: {error}")),
+        }
+
+        config.apply_args(args)?;
+
+        Ok(config)
+    }
+
+    pub fn rules(&self) -> Rules {
+        let scoring_mode = if self.cumulative {
+            ScoringMode::Vegas
+        } else {
+            self.scoring_mode
+        };
+
+        let passes_limit = match self.passes {
+            Passes::ByScoring => scoring_mode.default_passes_limit(self.draw_count),
+            Passes::Unlimited => None,
+            Passes::Limited(passes) => Some(passes),
+        };
+
+        Rules {
+            stacks_count: self.stacks_count,
+            draw_count: self.draw_count,
+            passes_limit,
+            scoring_mode,
+        }
+    }
+
+    pub fn winnable_deals(&self) -> Option<WinnableDeals> {
+        self.winnable.then_some(WinnableDeals {
+            attempts: self.attempts,
+            budget: Budget {
+                max_nodes: usize::MAX,
+                max_time: Duration::from_secs(self.solve_seconds),
+            },
+        })
+    }
+
+    fn apply_file(&mut self, contents: &str) -> Result<(), String> {
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let result = match line.split_once('=') {
+                Some((name, value)) => self.set(name.trim(), value.trim()),
+                None => Err(format!("Expected 'name = value', found '{line}'")),
+            };
+
+            result.map_err(|error| format!("Config file, line {}: {error}", i + 1))?;
+        }
+
+        Ok(())
+    }
+
+    fn apply_args(&mut self, args: impl IntoIterator<Item = String>) -> Result<(), String> {
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if let "--draw" | "--passes" | "--columns" | "--vegas" | "--cumulative" = arg.as_str() {
+                self.has_rule_options = true;
+            }
+
+            match arg.as_str() {
+                "--seed" => self.seed = Some(parse_value(&arg, &next_value(&arg, &mut args)?)?),
+                "--deal" => self.deal = Some(parse_value(&arg, &next_value(&arg, &mut args)?)?),
+                "--board" => self.board = Some(next_value(&arg, &mut args)?),
+                "--replay" => self.replay = Some(next_value(&arg, &mut args)?),
+                "--draw" | "--passes" | "--columns" | "--attempts" | "--solve-time" | "--theme" => {
+                    let value = next_value(&arg, &mut args)?;
+                    self.set(&arg[2..], &value)?;
+                }
+                "--vegas" => self.scoring_mode = ScoringMode::Vegas,
+                "--cumulative" => self.cumulative = true,
+                "--winnable" => self.winnable = true,
+                "--solve" => self.solve = true,
+                "--print-board" => self.print_board = true,
+                "-h" | "--help" => self.help = true,
+                _ => return Err(format!("Unknown option '{arg}'")),
+            }
+        }
+
+        let deals_count = [
+            self.seed.is_some(),
+            self.deal.is_some(),
+            self.board.is_some(),
+        ]
+        .into_iter()
+        .filter(|&is_set| is_set)
+        .count();
+        if deals_count > 1 {
+            return Err("Only one of '--seed', '--deal' and '--board' can be used".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "draw" => self.draw_count = parse_in_range(name, value, DRAW_COUNTS)?,
+            "columns" => self.stacks_count = parse_in_range(name, value, 1..=MAX_STACKS_COUNT)?,
+            "passes" => {
+                self.passes = match value {
+                    "unlimited" => Passes::Unlimited,
+                    _ => Passes::Limited(parse_in_range(name, value, 1..=usize::MAX)?),
+                }
+            }
+            "scoring" => {
+                self.scoring_mode = match value {
+                    "standard" => ScoringMode::Standard,
+                    "vegas" => ScoringMode::Vegas,
+                    _ => return Err(invalid_value(name, value)),
+                }
+            }
+            "cumulative" => self.cumulative = parse_value(name, value)?,
+            "winnable" => self.winnable = parse_value(name, value)?,
+            "theme" => self.theme = parse_value(name, value)?,
+            "attempts" => self.attempts = parse_in_range(name, value, 1..=usize::MAX)?,
+            "solve-time" => {
+                self.solve_seconds = parse_in_range(name, value, 1..=usize::MAX)? as u64
+            }
+            _ => return Err(format!("Unknown setting '{name}'")),
+        }
+
+        Ok(())
+    }
+}
+
+fn next_value(option: &str, args: &mut impl Iterator<Item = String>) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("Option '{option}' needs a value"))
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| invalid_value(name, value))
+}
+
+fn parse_in_range(name: &str, value: &str, range: RangeInclusive<usize>) -> Result<usize, String> {
+    let number = parse_value(name, value)?;
+
+    if range.contains(&number) {
+        Ok(number)
+    } else {
+        Err(invalid_value(name, value))
+    }
+}
+
+fn invalid_value(name: &str, value: &str) -> String {
+    format!("Invalid value '{value}' for '{name}'")
+}
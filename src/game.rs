@@ -1,16 +1,50 @@
+mod backend;
+mod bankroll;
+mod board;
 mod card;
 mod card_collections;
+mod cursor;
+mod deal;
 mod engine;
+mod game_record;
+mod history;
+mod layout;
+mod moves;
 mod renderer;
-
+mod replay;
+mod rng;
+mod rules;
+mod save;
+mod scoring;
+mod screen;
+mod solver;
+mod stats;
+mod stopwatch;
+mod terminal;
+mod theme;
+
+use std::{io::Result, time::Duration};
+
+pub use bankroll::Bankroll;
 use card::{Card, CoveringOrder};
 use card_collections::{Deck, Pile, Stack};
+pub use deal::Deal;
 use engine::GameEngine;
+pub use game_record::GameRecord;
+use history::{History, Record};
+use moves::Move;
+pub use rules::{Rules, MAX_STACKS_COUNT};
+pub use scoring::ScoringMode;
+use scoring::{format_dollars, Scoring};
+pub use solver::{Budget, Solution, WinnableDeals};
+use stopwatch::Stopwatch;
+pub use theme::Theme;
 
 const CARDS_IN_SUIT: usize = 13;
-const CARDS_IN_DECK: usize = CARDS_IN_SUIT * 4;
+const SUIT_STACKS_COUNT: usize = 4;
+const CARDS_IN_DECK: usize = CARDS_IN_SUIT * SUIT_STACKS_COUNT;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum GameObject {
     Deck,
     Pile,
@@ -22,24 +56,43 @@ enum GameObject {
 
 impl GameObject {
     pub fn is_none(&self) -> bool {
-        match self {
-            Self::None => true,
-            _ => false,
-        }
+        matches!(self, Self::None)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Game {
     deck: Deck,
-    suit_stacks: [Stack; 4],
+    suit_stacks: [Stack; SUIT_STACKS_COUNT],
     stacks: Vec<Stack>,
     pile: Pile,
+    deal: Option<Deal>,
+    rules: Rules,
+    history: History,
+    moves_count: u32,
+    stopwatch: Stopwatch,
+    scoring: Scoring,
+    passes: usize,
+    bankroll: Option<Bankroll>,
+    settled_score: i32,
+    is_counted_in_stats: bool,
+    winnable_deals: Option<WinnableDeals>,
 }
 
 impl Game {
-    pub fn new(stacks_count: usize, pile_size: usize) -> Self {
-        let pile = Pile::new(pile_size, CARDS_IN_DECK);
+    pub fn new(rules: Rules, deal: Deal) -> Self {
+        let mut game = Self::empty(rules, Some(deal));
+
+        game.deck = deal.deck();
+        Self::rearange_cards(&mut game.deck, &mut game.stacks);
+
+        game
+    }
+
+    /// A game with no cards on the table yet. The deal is `None` when the
+    /// cards are laid out from a board instead.
+    fn empty(rules: Rules, deal: Option<Deal>) -> Self {
+        let pile = Pile::new(rules.draw_count, CARDS_IN_DECK);
         let suit_stacks = [
             Stack::with_capacity(CARDS_IN_SUIT),
             Stack::with_capacity(CARDS_IN_SUIT),
@@ -47,34 +100,70 @@ impl Game {
             Stack::with_capacity(CARDS_IN_SUIT),
         ];
 
-        let mut deck = Self::generate_deck();
-        let mut stacks: Vec<Stack> = (0..stacks_count)
+        let deck = Deck::from_cards(Stack::with_capacity(CARDS_IN_DECK));
+        let stacks: Vec<Stack> = (0..rules.stacks_count)
             .map(|_| Stack::with_capacity(CARDS_IN_SUIT))
             .collect();
 
-        Self::rearange_cards(&mut deck, &mut stacks);
-
         Self {
             deck,
             suit_stacks,
             stacks,
             pile,
+            deal,
+            rules,
+            history: History::default(),
+            moves_count: 0,
+            stopwatch: Stopwatch::started(),
+            scoring: Scoring::new(rules.scoring_mode, rules.draw_count),
+            passes: 1,
+            bankroll: None,
+            settled_score: 0,
+            is_counted_in_stats: false,
+            winnable_deals: None,
         }
     }
 
-    pub fn start(&mut self) {
-        let mut engine = GameEngine::new(self);
+    pub fn with_bankroll(mut self, bankroll: Bankroll) -> Self {
+        self.bankroll = Some(bankroll);
+        self
+    }
+
+    pub fn with_winnable_deals(mut self, winnable_deals: WinnableDeals) -> Self {
+        self.winnable_deals = Some(winnable_deals);
+        self
+    }
+
+    pub fn start(&mut self, theme: Theme) {
+        let result = GameEngine::new(self, theme).start();
 
-        if let Err(error) = engine.start() {
+        if let Err(error) = result {
             println!("Error during the game: {error}");
         }
+
+        if let Err(error) = self.settle_bankroll() {
+            println!("Error saving the bankroll: {error}");
+        }
     }
 
-    fn generate_deck() -> Deck {
-        Deck::random()
+    pub fn solve(&self, budget: Budget) -> Solution {
+        solver::solve(self, budget)
     }
 
-    fn rearange_cards(deck: &mut Deck, stacks: &mut Vec<Stack>) {
+    pub fn deal_text(&self) -> String {
+        match self.deal {
+            Some(deal) => deal.to_string(),
+            None => "Custom board".to_string(),
+        }
+    }
+
+    /// The deal a new one is dealt like: a seed for a seed, a numbered deal
+    /// for a numbered one.
+    fn get_deal_like_last(&self) -> Deal {
+        self.deal.unwrap_or_else(Deal::random)
+    }
+
+    fn rearange_cards(deck: &mut Deck, stacks: &mut [Stack]) {
         for (i, stack) in stacks.iter_mut().enumerate() {
             for _ in 0..i {
                 deck.pop_into(stack);
@@ -88,22 +177,355 @@ impl Game {
         }
     }
 
-    fn restart(&mut self) {
+    /// The new deal is dealt even when the bankroll can't be saved, the
+    /// error is handed back to be reported once the terminal is free.
+    fn restart(&mut self, deal: Deal) -> Result<()> {
+        let settled = self.settle_bankroll();
+
         self.stacks.iter_mut().for_each(|stack| stack.clear());
         self.suit_stacks.iter_mut().for_each(|stack| stack.clear());
         self.pile.clear();
-
-        let mut deck = Self::generate_deck();
+        self.history.clear();
+        self.moves_count = 0;
+        self.stopwatch.restart();
+        self.scoring.reset();
+        self.settled_score = 0;
+        self.is_counted_in_stats = false;
+        self.passes = 1;
+
+        self.deal = Some(deal);
+        let mut deck = deal.deck();
         Self::rearange_cards(&mut deck, &mut self.stacks);
 
         self.deck = deck;
+
+        settled
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.suit_stacks
+            .iter()
+            .all(|stack| stack.len() == CARDS_IN_SUIT)
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    pub fn moves_count(&self) -> u32 {
+        self.moves_count
+    }
+
+    pub fn score(&self) -> i32 {
+        self.scoring.score()
+    }
+
+    pub fn score_text(&self) -> String {
+        self.scoring.to_string()
+    }
+
+    pub fn bankroll_text(&self) -> Option<String> {
+        let bankroll = self.bankroll.as_ref()?;
+        let unsettled_score = self.score() - self.settled_score;
+        Some(format_dollars(bankroll.balance() + unsettled_score as i64))
+    }
+
+    pub fn passes_text(&self) -> Option<String> {
+        let limit = self.rules.passes_limit?;
+        Some(format!("{}/{limit}", self.passes))
+    }
+
+    /// Only the part of the score not settled yet goes to the bankroll, so
+    /// settling can be done any number of times.
+    fn settle_bankroll(&mut self) -> Result<()> {
+        let Some(bankroll) = self.bankroll.as_mut() else {
+            return Ok(());
+        };
+
+        let score = self.scoring.score();
+        bankroll.settle((score - self.settled_score) as i64)?;
+        self.settled_score = score;
+
+        Ok(())
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.stopwatch.elapsed()
+    }
+
+    /// With the deck and the pile empty and every card face up, the game is
+    /// won by sending cards to the suit stacks one by one.
+    pub fn can_auto_complete(&self) -> bool {
+        self.deck.is_empty()
+            && self.pile.is_empty()
+            && !self.is_won()
+            && self
+                .stacks
+                .iter()
+                .all(|stack| stack.get_all().iter().all(|card| !card.hidden))
+    }
+
+    fn find_auto_complete_move(&self) -> Option<Move> {
+        (0..self.stacks.len())
+            .filter_map(|from| {
+                let card = self.stacks[from].last()?;
+                let into = self.find_suit_stack_accepting(Some(card))?;
+                Some((u8::from(&card.value), Move::StackToSuitStack { from, into }))
+            })
+            .min_by_key(|(value, _)| *value)
+            .map(|(_, mv)| mv)
+    }
+
+    fn auto_complete_step(&mut self) -> bool {
+        match self.find_auto_complete_move() {
+            Some(mv) => self.make_move(mv),
+            None => false,
+        }
+    }
+
+    /// Sends the card of the object to a suit stack that accepts it or, when
+    /// there is none, to the best stack: one that isn't empty if possible.
+    fn auto_move(&mut self, object: GameObject) -> bool {
+        match self.find_auto_move(object) {
+            Some(mv) => self.make_move(mv),
+            None => false,
+        }
+    }
+
+    fn find_auto_move(&self, object: GameObject) -> Option<Move> {
+        match object {
+            GameObject::Pile => {
+                let card = self.pile.get_visible_cards().last();
+
+                self.find_suit_stack_accepting(card)
+                    .map(Move::PileToSuitStack)
+                    .or_else(|| self.find_stack_accepting(card, None).map(Move::PileToStack))
+            }
+            GameObject::LastCardOfStack(i) => {
+                let from = i as usize;
+                let card = self.stacks.get(from)?.last();
+
+                self.find_suit_stack_accepting(card)
+                    .map(|into| Move::StackToSuitStack { from, into })
+                    .or_else(|| self.find_auto_move_of_cards_from_stack(from, 1))
+            }
+            GameObject::CardOfStack { stack_i, card_i } => {
+                let from = stack_i as usize;
+                let count = self.stacks.get(from)?.len().checked_sub(card_i as usize)?;
+
+                self.find_auto_move_of_cards_from_stack(from, count)
+            }
+            _ => None,
+        }
+    }
+
+    fn find_auto_move_of_cards_from_stack(&self, from: usize, count: usize) -> Option<Move> {
+        let cards = self.stacks[from].get_all();
+        let starting_from_card_i = cards.len().checked_sub(count)?;
+        let card = cards.get(starting_from_card_i);
+
+        let into = if starting_from_card_i == 0 {
+            self.find_stack_accepting(card, Some(from))
+                .filter(|&into| !self.stacks[into].is_empty())
+        } else {
+            self.find_stack_accepting(card, Some(from))
+        };
+
+        into.map(|into| Move::StackToStack { from, into, count })
+    }
+
+    fn find_suit_stack_accepting(&self, card: Option<&Card>) -> Option<usize> {
+        self.suit_stacks_accepting(card).first().copied()
+    }
+
+    fn find_stack_accepting(&self, card: Option<&Card>, except: Option<usize>) -> Option<usize> {
+        let accepting = self.stacks_accepting(card, except);
+
+        accepting
+            .iter()
+            .find(|&&i| !self.stacks[i].is_empty())
+            .or(accepting.first())
+            .copied()
+    }
+
+    pub fn make_move(&mut self, mv: Move) -> bool {
+        match self.apply_move(mv) {
+            Some(record) => {
+                self.history.push(record);
+                self.on_move_made(&record);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop_done() {
+            Some(record) => {
+                self.revert_move(record);
+                self.scoring = record.scoring;
+                true
+            }
+            None => false,
+        }
     }
 
-    fn move_cards_from_deck_to_pile(&mut self) {
+    pub fn redo(&mut self) -> bool {
+        let Some(mv) = self.history.pop_undone() else {
+            return false;
+        };
+
+        match self.apply_move(mv) {
+            Some(record) => {
+                self.history.push_redone(record);
+                self.on_move_made(&record);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn on_move_made(&mut self, record: &Record) {
+        self.moves_count += 1;
+        self.scoring.on_move(record);
+
+        if self.is_won() {
+            self.stopwatch.stop();
+            self.scoring.on_win(self.stopwatch.elapsed());
+        }
+    }
+
+    fn apply_move(&mut self, mv: Move) -> Option<Record> {
+        let revealed = match mv {
+            Move::StackToStack { from, count, .. } => self.will_reveal_card(from, count),
+            Move::StackToSuitStack { from, .. } => self.will_reveal_card(from, 1),
+            _ => false,
+        };
+
+        let (moved, drawn) = match mv {
+            Move::Draw => match self.move_cards_from_deck_to_pile() {
+                Some(drawn) => (true, drawn),
+                None => (false, 0),
+            },
+            Move::PileToStack(i) => (self.move_card_from_pile_to_stack(i), 0),
+            Move::PileToSuitStack(i) => (self.move_card_from_pile_to_suit_stack(i), 0),
+            Move::StackToStack { from, into, count } => {
+                let starting_from_card_i = self.stacks.get(from).map_or(0, Stack::len);
+                let moved = count > 0
+                    && count <= starting_from_card_i
+                    && self.move_cards_from_stack_to_stack(
+                        from,
+                        into,
+                        starting_from_card_i - count,
+                    );
+                (moved, 0)
+            }
+            Move::StackToSuitStack { from, into } => {
+                (self.move_card_from_stack_to_suit_stack(from, into), 0)
+            }
+            Move::SuitStackToStack { from, into } => {
+                (self.move_card_from_suit_stack_to_stack(from, into), 0)
+            }
+        };
+
+        moved.then_some(Record {
+            mv,
+            revealed,
+            drawn,
+            scoring: self.scoring,
+        })
+    }
+
+    fn revert_move(&mut self, record: Record) {
+        match record.mv {
+            Move::Draw if record.drawn == 0 => {
+                self.pile.pull_all_from(&mut self.deck);
+                self.passes -= 1;
+            }
+            Move::Draw => self.pile.pop_n_into(record.drawn, &mut self.deck),
+            Move::PileToStack(i) => self.stacks[i].pop_into(self.pile.get_cards_mut()),
+            Move::PileToSuitStack(i) => self.suit_stacks[i].pop_into(self.pile.get_cards_mut()),
+            Move::StackToStack { from, into, count } => {
+                if record.revealed {
+                    self.stacks[from].hide_last();
+                }
+                let mut temp_stack = Stack::with_capacity(count);
+                self.stacks[into].move_n_last_into(count, &mut temp_stack);
+                temp_stack.move_n_last_into(count, &mut self.stacks[from]);
+            }
+            Move::StackToSuitStack { from, into } => {
+                if record.revealed {
+                    self.stacks[from].hide_last();
+                }
+                self.suit_stacks[into].pop_into(&mut self.stacks[from]);
+            }
+            Move::SuitStackToStack { from, into } => {
+                self.stacks[into].pop_into(&mut self.suit_stacks[from]);
+            }
+        }
+    }
+
+    /// Whether the cards the record would take back are where it says, so
+    /// that a record read from a file can be reverted without failing.
+    fn can_revert_move(&self, record: &Record) -> bool {
+        let stacks_count = self.stacks.len();
+        let has_cards = |stacks: &[Stack], i: usize, count: usize| {
+            stacks.get(i).is_some_and(|s| s.len() >= count)
+        };
+
+        match record.mv {
+            Move::Draw if record.drawn == 0 => self.pile.is_empty() && self.passes > 1,
+            Move::Draw => record.drawn <= self.pile.size() && self.pile.len() >= record.drawn,
+            Move::PileToStack(i) => has_cards(&self.stacks, i, 1),
+            Move::PileToSuitStack(i) => has_cards(&self.suit_stacks, i, 1),
+            Move::StackToStack { from, into, count } => {
+                from != into
+                    && from < stacks_count
+                    && count > 0
+                    && has_cards(&self.stacks, into, count)
+                    && (!record.revealed || has_cards(&self.stacks, from, 1))
+            }
+            Move::StackToSuitStack { from, into } => {
+                has_cards(&self.suit_stacks, into, 1)
+                    && from < stacks_count
+                    && (!record.revealed || has_cards(&self.stacks, from, 1))
+            }
+            Move::SuitStackToStack { from, into } => {
+                from < self.suit_stacks.len() && has_cards(&self.stacks, into, 1)
+            }
+        }
+    }
+
+    fn will_reveal_card(&self, i: usize, count: usize) -> bool {
+        let Some(stack) = self.stacks.get(i) else {
+            return false;
+        };
+        let cards = stack.get_all();
+
+        cards
+            .len()
+            .checked_sub(count + 1)
+            .is_some_and(|card_i| cards[card_i].hidden)
+    }
+
+    fn can_recycle(&self) -> bool {
+        !self.pile.is_empty()
+            && self
+                .rules
+                .passes_limit
+                .is_none_or(|limit| self.passes < limit)
+    }
+
+    fn move_cards_from_deck_to_pile(&mut self) -> Option<usize> {
         if self.deck.is_empty() {
+            if !self.can_recycle() {
+                return None;
+            }
             self.pile.pop_all_into(&mut self.deck);
+            self.passes += 1;
+            Some(0)
         } else {
-            self.pile.pull_from(&mut self.deck);
+            Some(self.pile.pull_from(&mut self.deck))
         }
     }
 
@@ -142,22 +564,6 @@ impl Game {
         }
     }
 
-    fn move_card_from_stack_to_stack(&mut self, i: usize, j: usize) -> bool {
-        if i == j || i >= self.stacks.len() || j >= self.stacks.len() {
-            return false;
-        }
-
-        let (left, right) = self.stacks.split_at_mut(j.max(i));
-
-        let (from, into) = if i < j {
-            (&mut left[i], &mut right[0])
-        } else {
-            (&mut right[0], &mut left[j])
-        };
-
-        Self::pop_card_from_stack_into_stack(from, into, CoveringOrder::Descending)
-    }
-
     fn move_card_from_stack_to_suit_stack(&mut self, i: usize, j: usize) -> bool {
         if let Some(from) = self.stacks.get_mut(i) {
             if let Some(into) = self.suit_stacks.get_mut(j) {
@@ -220,6 +626,6 @@ impl Game {
 
 impl Default for Game {
     fn default() -> Self {
-        Game::new(7, 3)
+        Game::new(Rules::default(), Deal::random())
     }
 }
@@ -0,0 +1,187 @@
+use std::io::{stdout, Result, Stdout, Write};
+
+#[cfg(test)]
+use crossterm::style::Color;
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Print, SetForegroundColor},
+    terminal::{size, Clear, ClearType},
+};
+
+use super::{screen::Cell, terminal::TerminalSession};
+
+/// Where the frames drawn by the renderer end up.
+pub trait Backend {
+    /// The size of the screen as columns and rows.
+    fn size(&self) -> Result<(u16, u16)>;
+
+    fn start(&mut self) -> Result<()>;
+
+    fn stop(&mut self);
+
+    /// Blanks the whole screen, before the cells of a frame are drawn.
+    fn clear(&mut self) -> Result<()>;
+
+    /// Draws the cells that changed since the last frame, each with its
+    /// column and row.
+    fn draw(&mut self, cells: &[(u16, u16, Cell)]) -> Result<()>;
+}
+
+/// Everything of a frame is queued and written at once, so that the
+/// terminal never shows half of it.
+pub struct TerminalBackend {
+    out: Stdout,
+    buffer: Vec<u8>,
+    session: Option<TerminalSession>,
+}
+
+impl TerminalBackend {
+    pub fn new() -> Self {
+        Self {
+            out: stdout(),
+            buffer: Vec::new(),
+            session: None,
+        }
+    }
+}
+
+impl Backend for TerminalBackend {
+    fn size(&self) -> Result<(u16, u16)> {
+        size()
+    }
+
+    fn start(&mut self) -> Result<()> {
+        self.session = Some(TerminalSession::start()?);
+
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.session = None;
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        queue!(self.buffer, Clear(ClearType::All))
+    }
+
+    fn draw(&mut self, cells: &[(u16, u16, Cell)]) -> Result<()> {
+        let mut color = None;
+        let mut position = None;
+
+        for &(x, y, cell) in cells {
+            if position != Some((x, y)) {
+                queue!(self.buffer, MoveTo(x, y))?;
+            }
+            if color != Some(cell.color) {
+                queue!(self.buffer, SetForegroundColor(cell.color))?;
+                color = Some(cell.color);
+            }
+
+            queue!(self.buffer, Print(cell.symbol))?;
+            position = Some((x + 1, y));
+        }
+
+        let result = self.out.write_all(&self.buffer);
+        self.buffer.clear();
+
+        result.and_then(|_| self.out.flush())
+    }
+}
+
+/// Keeps the screen in memory instead of drawing it, so that what the
+/// renderer draws can be looked at without a terminal.
+#[cfg(test)]
+pub struct HeadlessBackend {
+    columns: u16,
+    rows: u16,
+    cells: Vec<Cell>,
+}
+
+#[cfg(test)]
+impl HeadlessBackend {
+    pub fn new(columns: u16, rows: u16) -> Self {
+        Self {
+            columns,
+            rows,
+            cells: vec![Cell::default(); columns as usize * rows as usize],
+        }
+    }
+
+    pub fn get_cell(&self, x: u16, y: u16) -> Cell {
+        self.cells[y as usize * self.columns as usize + x as usize]
+    }
+
+    /// The symbols of the screen, then the same rows with a letter for the
+    /// color of every cell and what color each letter stands for. Blank
+    /// cells are left out of the colors, trailing spaces of every row too.
+    pub fn get_snapshot(&self) -> String {
+        let mut colors: Vec<Color> = Vec::new();
+        let mut symbols_text = String::new();
+        let mut colors_text = String::new();
+
+        for y in 0..self.rows {
+            let mut symbols_row = String::new();
+            let mut colors_row = String::new();
+
+            for x in 0..self.columns {
+                let cell = self.get_cell(x, y);
+                symbols_row.push(cell.symbol);
+
+                if cell == Cell::default() {
+                    colors_row.push(' ');
+                    continue;
+                }
+
+                let i = match colors.iter().position(|&color| color == cell.color) {
+                    Some(i) => i,
+                    None => {
+                        colors.push(cell.color);
+                        colors.len() - 1
+                    }
+                };
+                colors_row.push((b'a' + i as u8) as char);
+            }
+
+            symbols_text += symbols_row.trim_end();
+            symbols_text.push('\n');
+            colors_text += colors_row.trim_end();
+            colors_text.push('\n');
+        }
+
+        let legend: String = colors
+            .iter()
+            .enumerate()
+            .map(|(i, color)| format!("{} = {color:?}\n", (b'a' + i as u8) as char))
+            .collect();
+
+        format!("{symbols_text}\n{colors_text}\n{legend}")
+    }
+}
+
+#[cfg(test)]
+impl Backend for HeadlessBackend {
+    fn size(&self) -> Result<(u16, u16)> {
+        Ok((self.columns, self.rows))
+    }
+
+    fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn stop(&mut self) {}
+
+    fn clear(&mut self) -> Result<()> {
+        self.cells.fill(Cell::default());
+
+        Ok(())
+    }
+
+    fn draw(&mut self, cells: &[(u16, u16, Cell)]) -> Result<()> {
+        for &(x, y, cell) in cells {
+            self.cells[y as usize * self.columns as usize + x as usize] = cell;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use std::io::{Error, ErrorKind, Result};
+
+use crate::storage;
+
+const BANKROLL_FILE: &str = "bankroll";
+
+/// Vegas balance carried over from one deal to the next and kept on disk
+/// between sessions.
+#[derive(Debug, Clone)]
+pub struct Bankroll {
+    balance: i64,
+}
+
+impl Bankroll {
+    pub fn load() -> Result<Self> {
+        let balance = match storage::read(BANKROLL_FILE)? {
+            Some(contents) => contents
+                .trim()
+                .parse()
+                .map_err(|_| Error::new(ErrorKind::InvalidData, "bankroll file is corrupted"))?,
+            None => 0,
+        };
+
+        Ok(Self { balance })
+    }
+
+    pub fn balance(&self) -> i64 {
+        self.balance
+    }
+
+    pub fn settle(&mut self, amount: i64) -> Result<()> {
+        self.balance += amount;
+        storage::write(BANKROLL_FILE, &format!("{}\n", self.balance))
+    }
+}
+
+/// A bankroll that was never saved, so that tests don't touch the player's.
+#[cfg(test)]
+impl Bankroll {
+    pub fn with_balance(balance: i64) -> Self {
+        Self { balance }
+    }
+}
@@ -0,0 +1,210 @@
+use std::{collections::HashSet, fmt};
+
+use super::{
+    card::Card,
+    card_collections::{Deck, Pile, Stack},
+    Game, Rules, CARDS_IN_DECK, CARDS_IN_SUIT, MAX_STACKS_COUNT, SUIT_STACKS_COUNT,
+};
+
+const FOUNDATIONS_LABEL: &str = "foundations:";
+const PILE_LABEL: &str = "pile:";
+const DECK_LABEL: &str = "deck:";
+const EMPTY_FOUNDATION: &str = "-";
+
+/// The position as text, for example:
+///
+/// ```text
+/// foundations: 2♣ - A♥ -
+/// pile: 5♠ K♦
+/// deck: 4♦ J♠ 9♣
+/// 1: K♥ Q♣
+/// 2: #10♠ #3♦ 7♥
+/// ```
+///
+/// Cards are listed from the bottom up, so the last card of the deck is
+/// drawn first. Foundations only show their top card and `-` when empty.
+impl fmt::Display for Game {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let foundations: Vec<String> = self
+            .suit_stacks
+            .iter()
+            .map(|stack| match stack.last() {
+                Some(card) => card.to_string(),
+                None => EMPTY_FOUNDATION.to_string(),
+            })
+            .collect();
+
+        writeln!(formatter, "{FOUNDATIONS_LABEL} {}", foundations.join(" "))?;
+        writeln!(
+            formatter,
+            "{}",
+            get_cards_line(PILE_LABEL, self.pile.get_cards())
+        )?;
+        writeln!(
+            formatter,
+            "{}",
+            get_cards_line(DECK_LABEL, self.deck.get_cards())
+        )?;
+
+        for (i, stack) in self.stacks.iter().enumerate() {
+            writeln!(
+                formatter,
+                "{}",
+                get_cards_line(&format!("{}:", i + 1), stack)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Game {
+    /// Sets the game up from a board printed by [`Game`]'s `Display`. The
+    /// number of stacks comes from the board, the rest of the rules from
+    /// `rules`. Blank lines and lines starting with `//` are skipped. Cards
+    /// of the deck and the pile are turned face up, as the game keeps them,
+    /// even when they are written face down.
+    pub fn from_board(rules: Rules, text: &str) -> Result<Game, String> {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("//"))
+            .collect();
+
+        let [foundations, pile, deck, stacks @ ..] = lines.as_slice() else {
+            return Err("Expected foundations, pile, deck and stack lines".to_string());
+        };
+
+        let suit_stacks = parse_foundations(strip_label(foundations, FOUNDATIONS_LABEL)?)?;
+        let pile = parse_cards(strip_label(pile, PILE_LABEL)?)?;
+        let deck = parse_cards(strip_label(deck, DECK_LABEL)?)?;
+        let stacks: Vec<Stack> = stacks
+            .iter()
+            .enumerate()
+            .map(|(i, line)| parse_cards(strip_label(line, &format!("{}:", i + 1))?))
+            .collect::<Result<_, _>>()?;
+
+        if !(1..=MAX_STACKS_COUNT).contains(&stacks.len()) {
+            return Err(format!(
+                "Expected 1 to {MAX_STACKS_COUNT} stacks, found {}",
+                stacks.len()
+            ));
+        }
+
+        let rules = Rules {
+            stacks_count: stacks.len(),
+            ..rules
+        };
+        let mut game = Game::empty(rules, None);
+
+        game.suit_stacks = suit_stacks;
+        game.pile = Pile::with_cards(rules.draw_count, pile);
+        game.pile.get_cards_mut().reveal_all();
+        game.deck = Deck::from_cards(deck);
+        game.deck.reveal_all();
+        game.stacks = stacks;
+        game.stacks.iter_mut().for_each(Stack::reveal_last);
+
+        game.check_cards()?;
+
+        Ok(game)
+    }
+
+    /// A board or a save edited by hand could lose or duplicate cards, which
+    /// the game can't cope with.
+    pub(super) fn check_cards(&self) -> Result<(), String> {
+        let cards: Vec<&Card> = self
+            .stacks
+            .iter()
+            .chain(&self.suit_stacks)
+            .chain([self.deck.get_cards(), self.pile.get_cards()])
+            .flat_map(|stack| stack.get_all())
+            .collect();
+
+        let unique_cards: HashSet<(u8, u8)> = cards
+            .iter()
+            .map(|card| (u8::from(&card.value), card.suit.clone() as u8))
+            .collect();
+
+        if cards.len() != CARDS_IN_DECK || unique_cards.len() != CARDS_IN_DECK {
+            return Err("The cards don't make up a full deck".to_string());
+        }
+
+        self.check_face_down_cards()
+    }
+
+    /// Only the bottom of a stack can be face down, the game never turns
+    /// any other card over.
+    fn check_face_down_cards(&self) -> Result<(), String> {
+        let is_any_face_down = |stack: &Stack| stack.get_all().iter().any(|card| card.hidden);
+        if is_any_face_down(self.deck.get_cards()) || is_any_face_down(self.pile.get_cards()) {
+            return Err("The deck and the pile can't hold face down cards".to_string());
+        }
+
+        for (i, stack) in self.stacks.iter().enumerate() {
+            let cards = stack.get_all();
+            if let Some(pair) = cards
+                .windows(2)
+                .find(|pair| !pair[0].hidden && pair[1].hidden)
+            {
+                return Err(format!(
+                    "Stack {} has the face down {} on the face up {}",
+                    i + 1,
+                    pair[1],
+                    pair[0]
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn strip_label<'a>(line: &'a str, label: &str) -> Result<&'a str, String> {
+    line.strip_prefix(label)
+        .map(str::trim)
+        .ok_or_else(|| format!("Expected a line starting with '{label}', found '{line}'"))
+}
+
+fn get_cards_line(label: &str, stack: &Stack) -> String {
+    stack
+        .get_all()
+        .iter()
+        .fold(label.to_string(), |line, card| format!("{line} {card}"))
+}
+
+pub(super) fn parse_cards(text: &str) -> Result<Stack, String> {
+    text.split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map(Stack::from_cards)
+}
+
+/// Every foundation is filled from the ace up to the card given for it.
+fn parse_foundations(text: &str) -> Result<[Stack; SUIT_STACKS_COUNT], String> {
+    let tops: Vec<&str> = text.split_whitespace().collect();
+    if tops.len() != SUIT_STACKS_COUNT {
+        return Err(format!(
+            "Expected {SUIT_STACKS_COUNT} foundations, found '{text}'"
+        ));
+    }
+
+    let mut suit_stacks: [Stack; SUIT_STACKS_COUNT] =
+        std::array::from_fn(|_| Stack::with_capacity(CARDS_IN_SUIT));
+
+    for (stack, top) in suit_stacks.iter_mut().zip(tops) {
+        if top == EMPTY_FOUNDATION {
+            continue;
+        }
+
+        let top: Card = top.parse()?;
+        for card in Card::suit_up_to(&top) {
+            stack.push(card);
+        }
+    }
+
+    Ok(suit_stacks)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,125 @@
+use crate::game::{Deal, Game, Rules, MAX_STACKS_COUNT};
+
+fn assert_same_position(game: &Game, other_game: &Game) {
+    assert_eq!(game.suit_stacks, other_game.suit_stacks);
+    assert_eq!(game.pile.get_cards(), other_game.pile.get_cards());
+    assert_eq!(game.deck, other_game.deck);
+    assert_eq!(game.stacks, other_game.stacks);
+}
+
+fn parse_board(text: &str) -> Result<Game, String> {
+    Game::from_board(Rules::default(), text)
+}
+
+/// A full deck laid out like [`Game`]'s `Display` prints it, to be spoiled
+/// by the tests one line at a time.
+const BOARD: &str = "\
+foundations: 2♣ - A♥ -
+pile: 5♠ K♦
+deck: 3♣ 4♣ 5♣ 6♣ 7♣ 8♣ 9♣ 10♣ J♣ K♣ A♦ 2♦ 3♦ 4♦ 5♦ 6♦ 7♦ 8♦ 9♦ 10♦ J♦ Q♦
+1: K♥ Q♣
+2: #A♠ #2♠ 3♠ 4♠
+3: 6♠ 7♠ 8♠ 9♠ 10♠ J♠ Q♠ K♠
+4: #2♥ #3♥ #4♥ 5♥ 6♥ 7♥ 8♥ 9♥ 10♥ J♥ Q♥
+";
+
+#[test]
+fn parses_formatted_boards() {
+    let mut game = Game::new(Rules::default(), Deal::Seeded(5));
+    let mut games = vec![game.clone()];
+    for _ in 0..30 {
+        let Some(&mv) = game.hints().first() else {
+            break;
+        };
+        game.make_move(mv);
+        games.push(game.clone());
+    }
+    games.push(parse_board(BOARD).expect("the board is valid"));
+
+    for game in games {
+        let board = game.to_string();
+        let parsed_game = parse_board(&board).expect("a printed board parses back");
+
+        assert_same_position(&game, &parsed_game);
+        assert_eq!(parsed_game.to_string(), board);
+    }
+}
+
+#[test]
+fn parses_boards_written_by_hand() {
+    let board = format!("// A comment\n\n{}", BOARD.replace("K♥ Q♣", "Kh Qc"));
+
+    let game = parse_board(&board).expect("the board is valid");
+
+    assert_same_position(&game, &parse_board(BOARD).expect("the board is valid"));
+}
+
+/// The deck is naturally written face down, but the game keeps its cards
+/// and those of the pile face up.
+#[test]
+fn turns_deck_and_pile_face_up() {
+    let board = BOARD
+        .replace("pile: 5♠ K♦", "pile: #5♠ #K♦")
+        .replace("deck: 3♣", "deck: #3♣");
+
+    let game = parse_board(&board).expect("the board is valid");
+
+    assert_same_position(&game, &parse_board(BOARD).expect("the board is valid"));
+}
+
+#[test]
+fn rejects_invalid_boards() {
+    let boards = [
+        ("", "Expected foundations, pile, deck and stack lines"),
+        (
+            &BOARD.replace("foundations: 2♣ - A♥ -", "foundations: 2♣ - A♥"),
+            "Expected 4 foundations, found '2♣ - A♥'",
+        ),
+        (
+            &BOARD.replace("pile:", "waste:"),
+            "Expected a line starting with 'pile:', found 'waste: 5♠ K♦'",
+        ),
+        (
+            &BOARD.replace("1: K♥", "3: K♥"),
+            "Expected a line starting with '1:', found '3: K♥ Q♣'",
+        ),
+        (&BOARD.replace("K♥", "K♥♥"), "Unknown card value 'K♥'"),
+        (&BOARD.replace("Q♣", "Q♪"), "Unknown suit '♪'"),
+        (
+            &BOARD.replace("K♥", "K♦"),
+            "The cards don't make up a full deck",
+        ),
+        (
+            &BOARD.replace(" Q♣\n", "\n"),
+            "The cards don't make up a full deck",
+        ),
+        (
+            &BOARD.replace("6♠ 7♠", "6♠ #7♠"),
+            "Stack 3 has the face down #7♠ on the face up 6♠",
+        ),
+        (
+            &BOARD.replace("5♥ 6♥", "5♥ #6♥"),
+            "Stack 4 has the face down #6♥ on the face up 5♥",
+        ),
+    ];
+
+    for (board, error) in boards {
+        assert_eq!(parse_board(board).err().as_deref(), Some(error), "{board}");
+    }
+}
+
+#[test]
+fn rejects_more_stacks_than_fit() {
+    let mut board = BOARD.to_string();
+    for i in 5..=MAX_STACKS_COUNT + 1 {
+        board += &format!("{i}:\n");
+    }
+
+    assert_eq!(
+        parse_board(&board).err(),
+        Some(format!(
+            "Expected 1 to {MAX_STACKS_COUNT} stacks, found {}",
+            MAX_STACKS_COUNT + 1
+        ))
+    );
+}
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 #[derive(PartialEq)]
 enum Color {
@@ -31,7 +31,7 @@ pub enum Value {
     Ace,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Card {
     pub value: Value,
     pub suit: Suit,
@@ -51,7 +51,7 @@ impl Suit {
         }
     }
 
-    fn has_same_color_with(&self, another: &Self) -> bool {
+    pub fn has_same_color_with(&self, another: &Self) -> bool {
         self.color() == another.color()
     }
 }
@@ -68,6 +68,22 @@ impl fmt::Display for Suit {
     }
 }
 
+/// Accepts the symbols shown on the cards as well as the letters `c`, `d`,
+/// `s` and `h`, which are easier to type.
+impl FromStr for Suit {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "♣" | "c" | "C" => Ok(Suit::Clubs),
+            "♦" | "d" | "D" => Ok(Suit::Diamonds),
+            "♠" | "s" | "S" => Ok(Suit::Spades),
+            "♥" | "h" | "H" => Ok(Suit::Hearts),
+            _ => Err(format!("Unknown suit '{text}'")),
+        }
+    }
+}
+
 impl Value {
     fn is_next_after(&self, value: &Self) -> bool {
         u8::from(self) == u8::from(value) + 1
@@ -95,6 +111,29 @@ impl fmt::Display for Value {
     }
 }
 
+impl FromStr for Value {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "2" => Ok(Value::Two),
+            "3" => Ok(Value::Three),
+            "4" => Ok(Value::Four),
+            "5" => Ok(Value::Five),
+            "6" => Ok(Value::Six),
+            "7" => Ok(Value::Seven),
+            "8" => Ok(Value::Eight),
+            "9" => Ok(Value::Nine),
+            "10" => Ok(Value::Ten),
+            "J" => Ok(Value::Jack),
+            "Q" => Ok(Value::Queen),
+            "K" => Ok(Value::King),
+            "A" => Ok(Value::Ace),
+            _ => Err(format!("Unknown card value '{text}'")),
+        }
+    }
+}
+
 impl From<&Value> for u8 {
     fn from(value: &Value) -> Self {
         match value {
@@ -115,6 +154,22 @@ impl From<&Value> for u8 {
     }
 }
 
+const VALUES_FROM_ACE: [Value; 13] = [
+    Value::Ace,
+    Value::Two,
+    Value::Three,
+    Value::Four,
+    Value::Five,
+    Value::Six,
+    Value::Seven,
+    Value::Eight,
+    Value::Nine,
+    Value::Ten,
+    Value::Jack,
+    Value::Queen,
+    Value::King,
+];
+
 impl Card {
     pub fn new(value: Value, suit: Suit) -> Self {
         Card {
@@ -124,6 +179,20 @@ impl Card {
         }
     }
 
+    /// Face up cards of the same suit from the ace up to `top`, the way they
+    /// lie on a suit stack.
+    pub fn suit_up_to(top: &Card) -> Vec<Card> {
+        VALUES_FROM_ACE
+            .iter()
+            .take(u8::from(&top.value) as usize)
+            .map(|value| Card {
+                value: value.clone(),
+                suit: top.suit.clone(),
+                hidden: false,
+            })
+            .collect()
+    }
+
     pub fn can_one_be_covered_with_another(
         card_to_cover: Option<&Card>,
         covering_card: Option<&Card>,
@@ -155,16 +224,41 @@ impl Card {
     pub fn reveal(&mut self) {
         self.hidden = false;
     }
+
+    pub fn hide(&mut self) {
+        self.hidden = true;
+    }
 }
 
+/// Value and suit, like `10♥`, with `#` in front of a face down card.
 impl fmt::Display for Card {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         let value = &self.value;
         let suit = &self.suit;
         if self.hidden {
-            write!(formatter, "▓")
+            write!(formatter, "#{value}{suit}")
         } else {
-            write!(formatter, "[{value}{suit}]")
+            write!(formatter, "{value}{suit}")
         }
     }
 }
+
+impl FromStr for Card {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (hidden, card) = match text.strip_prefix('#') {
+            Some(card) => (true, card),
+            None => (false, text),
+        };
+
+        let suit_i = card.char_indices().last().map_or(0, |(i, _)| i);
+        let (value, suit) = card.split_at(suit_i);
+
+        Ok(Card {
+            value: value.parse()?,
+            suit: suit.parse()?,
+            hidden,
+        })
+    }
+}
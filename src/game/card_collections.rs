@@ -1,15 +1,15 @@
-use super::card::Card;
+use super::{card::Card, rng::SplitMix64};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Stack(Vec<Card>);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Pile {
     cards: Stack,
     size: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Deck {
     cards: Stack,
 }
@@ -19,6 +19,10 @@ impl Stack {
         Self(Vec::with_capacity(capacity))
     }
 
+    pub fn from_cards(cards: Vec<Card>) -> Self {
+        Self(cards)
+    }
+
     pub fn push(&mut self, card: Card) {
         self.0.push(card);
     }
@@ -73,6 +77,17 @@ impl Stack {
         }
     }
 
+    pub fn hide_last(&mut self) {
+        if let Some(last) = self.0.last_mut() {
+            last.hide();
+        }
+    }
+
+    pub fn move_n_last_into(&mut self, n: usize, stack: &mut Stack) {
+        let start = self.0.len().saturating_sub(n);
+        stack.0.extend(self.0.drain(start..));
+    }
+
     pub fn get_all(&self) -> &[Card] {
         &self.0
     }
@@ -90,12 +105,29 @@ impl Pile {
         }
     }
 
-    pub fn pull_from(&mut self, deck: &mut Deck) {
+    pub fn with_cards(size: usize, cards: Stack) -> Self {
+        Self { cards, size }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn pull_from(&mut self, deck: &mut Deck) -> usize {
+        let mut pulled = 0;
         for _ in 0..self.size {
             if let Some(card) = deck.pop() {
                 self.cards.push(card);
+                pulled += 1;
             }
         }
+        pulled
+    }
+
+    pub fn pull_all_from(&mut self, deck: &mut Deck) {
+        while let Some(card) = deck.pop() {
+            self.cards.push(card);
+        }
     }
 
     pub fn pop_all_into(&mut self, deck: &mut Deck) {
@@ -104,16 +136,28 @@ impl Pile {
         }
     }
 
+    pub fn pop_n_into(&mut self, n: usize, deck: &mut Deck) {
+        for _ in 0..n {
+            if let Some(card) = self.cards.pop() {
+                deck.push(card);
+            }
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.cards.len()
     }
 
     pub fn get_visible_cards(&self) -> &[Card] {
         let end = self.len();
-        let start = end.checked_sub(self.size).unwrap_or(0);
+        let start = end.saturating_sub(self.size);
         &self.cards.get_all()[start..end]
     }
 
+    pub fn get_cards(&self) -> &Stack {
+        &self.cards
+    }
+
     pub fn get_cards_mut(&mut self) -> &mut Stack {
         &mut self.cards
     }
@@ -128,10 +172,12 @@ impl Pile {
 }
 
 impl Deck {
-    pub fn random() -> Self {
+    /// Deals the same deck for the same seed: cards are laid out in a fixed
+    /// order (values from two to ace, each in clubs, hearts, spades, diamonds)
+    /// and shuffled with [`SplitMix64`].
+    pub fn from_seed(seed: u64) -> Self {
         use super::card::{Suit::*, Value::*};
         use itertools::iproduct;
-        use rand::{seq::SliceRandom, thread_rng};
 
         let mut cards: Vec<Card> = iproduct!(
             [Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace],
@@ -140,13 +186,51 @@ impl Deck {
         .map(|(value, suit)| Card::new(value, suit))
         .collect();
 
-        cards.shuffle(&mut thread_rng());
+        SplitMix64::new(seed).shuffle(&mut cards);
 
         Deck {
             cards: Stack(cards),
         }
     }
 
+    /// Reproduces the numbered deals of the Windows card games: the linear
+    /// congruential generator of the Microsoft C runtime picks cards out of
+    /// a deck ordered by value (ace first) and then by suit (clubs, diamonds,
+    /// hearts, spades). The n-th picked card is the n-th card dealt from the
+    /// top of this deck.
+    pub fn microsoft(number: u32) -> Self {
+        use super::card::{Suit::*, Value::*};
+        use itertools::iproduct;
+
+        let mut cards: Vec<Card> = iproduct!(
+            [Ace, Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King],
+            [Clubs, Diamonds, Hearts, Spades]
+        )
+        .map(|(value, suit)| Card::new(value, suit))
+        .collect();
+
+        let mut state = number;
+        let mut next_random = || {
+            state = state.wrapping_mul(214013).wrapping_add(2531011) & 0x7fff_ffff;
+            state >> 16
+        };
+
+        let mut dealt = Vec::with_capacity(cards.len());
+        while !cards.is_empty() {
+            let i = next_random() as usize % cards.len();
+            dealt.push(cards.swap_remove(i));
+        }
+        dealt.reverse();
+
+        Deck {
+            cards: Stack(dealt),
+        }
+    }
+
+    pub fn from_cards(cards: Stack) -> Self {
+        Self { cards }
+    }
+
     pub fn push(&mut self, card: Card) {
         self.cards.push(card);
     }
@@ -170,4 +254,8 @@ impl Deck {
     pub fn reveal_all(&mut self) {
         self.cards.reveal_all();
     }
+
+    pub fn get_cards(&self) -> &Stack {
+        &self.cards
+    }
 }
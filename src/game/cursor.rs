@@ -0,0 +1,131 @@
+use super::{Game, GameObject, SUIT_STACKS_COUNT};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Keyboard cursor over the same objects that the mouse can point at. The
+/// top row goes suit stacks, pile, deck from left to right; below it every
+/// stack can be walked through its face up cards.
+pub struct Cursor {
+    object: GameObject,
+}
+
+impl Cursor {
+    pub fn new() -> Self {
+        Self {
+            object: GameObject::Deck,
+        }
+    }
+
+    /// Cards under the cursor can move away, so the position is brought back
+    /// to something that still exists before it's used.
+    pub fn get_object(&self, game: &Game) -> GameObject {
+        match self.object {
+            GameObject::CardOfStack { stack_i, card_i } => {
+                let cards = game.stacks[stack_i as usize].get_all();
+                let is_visible_not_last = (card_i as usize + 1) < cards.len()
+                    && cards.get(card_i as usize).is_some_and(|card| !card.hidden);
+
+                if is_visible_not_last {
+                    self.object
+                } else {
+                    GameObject::LastCardOfStack(stack_i)
+                }
+            }
+            object => object,
+        }
+    }
+
+    pub fn move_to(&mut self, game: &Game, direction: Direction) {
+        let object = self.get_object(game);
+        let stacks_count = game.stacks.len() as u16;
+
+        self.object = match (object, direction) {
+            (GameObject::CardOfStack { stack_i, .. } | GameObject::LastCardOfStack(stack_i), _) => {
+                let card_i = get_card_index(game, object);
+
+                match direction {
+                    Direction::Left if stack_i > 0 => GameObject::LastCardOfStack(stack_i - 1),
+                    Direction::Right if stack_i + 1 < stacks_count => {
+                        GameObject::LastCardOfStack(stack_i + 1)
+                    }
+                    Direction::Up => match card_i.checked_sub(1) {
+                        Some(card_i)
+                            if !game.stacks[stack_i as usize].get_all()[card_i as usize].hidden =>
+                        {
+                            GameObject::CardOfStack { stack_i, card_i }
+                        }
+                        _ => get_top_row_object_above(stack_i, stacks_count),
+                    },
+                    Direction::Down => {
+                        let last_i = game.stacks[stack_i as usize].len().saturating_sub(1) as u16;
+                        if card_i + 1 < last_i {
+                            GameObject::CardOfStack {
+                                stack_i,
+                                card_i: card_i + 1,
+                            }
+                        } else {
+                            GameObject::LastCardOfStack(stack_i)
+                        }
+                    }
+                    _ => object,
+                }
+            }
+            (_, Direction::Down) => {
+                GameObject::LastCardOfStack(get_stack_below(object, stacks_count))
+            }
+            (_, Direction::Left | Direction::Right) => {
+                let top_row = get_top_row();
+                let i = top_row.iter().position(|o| *o == object).unwrap_or(0);
+                let i = match direction {
+                    Direction::Left => i.saturating_sub(1),
+                    _ => (i + 1).min(top_row.len() - 1),
+                };
+                top_row[i]
+            }
+            (_, Direction::Up) => object,
+        };
+    }
+}
+
+fn get_top_row() -> Vec<GameObject> {
+    (0..SUIT_STACKS_COUNT as u16)
+        .map(GameObject::SuitStack)
+        .chain([GameObject::Pile, GameObject::Deck])
+        .collect()
+}
+
+fn get_card_index(game: &Game, object: GameObject) -> u16 {
+    match object {
+        GameObject::CardOfStack { card_i, .. } => card_i,
+        GameObject::LastCardOfStack(stack_i) => {
+            game.stacks[stack_i as usize].len().saturating_sub(1) as u16
+        }
+        _ => 0,
+    }
+}
+
+fn get_top_row_object_above(stack_i: u16, stacks_count: u16) -> GameObject {
+    if stack_i < SUIT_STACKS_COUNT as u16 {
+        GameObject::SuitStack(stack_i)
+    } else if stack_i + 1 == stacks_count {
+        GameObject::Deck
+    } else {
+        GameObject::Pile
+    }
+}
+
+fn get_stack_below(object: GameObject, stacks_count: u16) -> u16 {
+    let last_i = stacks_count.saturating_sub(1);
+
+    match object {
+        GameObject::SuitStack(i) => i.min(last_i),
+        GameObject::Pile => stacks_count.saturating_sub(2),
+        _ => last_i,
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt;
+
+use super::card_collections::Deck;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Deal {
+    Seeded(u64),
+    Microsoft(u32),
+}
+
+const MICROSOFT_CLASSIC_DEALS: u32 = 32000;
+
+impl Deal {
+    /// Random seeds are kept to 32 bits so they are short enough to read
+    /// off the screen and share.
+    pub fn random() -> Self {
+        Self::Seeded(rand::random::<u32>() as u64)
+    }
+
+    pub fn random_like(&self) -> Self {
+        match self {
+            Self::Seeded(_) => Self::random(),
+            Self::Microsoft(_) => {
+                Self::Microsoft(rand::random::<u32>() % MICROSOFT_CLASSIC_DEALS + 1)
+            }
+        }
+    }
+
+    pub fn deck(&self) -> Deck {
+        match *self {
+            Self::Seeded(seed) => Deck::from_seed(seed),
+            Self::Microsoft(number) => Deck::microsoft(number),
+        }
+    }
+}
+
+impl fmt::Display for Deal {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Seeded(seed) => write!(formatter, "Seed {seed}"),
+            Self::Microsoft(number) => write!(formatter, "Deal #{number}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+use crate::game::{Game, Rules};
+
+use super::Deal;
+
+/// FreeCell deal #1 as the Windows game lays it out, eight cards a row,
+/// in the order the cards are dealt.
+const MICROSOFT_DEAL_1: &str = "\
+J♦ 2♦ 9♥ J♣ 5♦ 7♥ 7♣ 5♥
+K♦ K♣ 9♠ 5♠ A♦ Q♣ K♥ 3♥
+2♠ K♠ 9♦ Q♦ J♠ A♠ A♥ 3♣
+4♣ 5♣ 10♠ Q♥ 4♥ A♣ 4♦ 7♠
+3♠ 10♦ 4♠ 10♥ 8♥ 2♣ J♥ 7♦
+6♦ 8♠ 8♦ Q♠ 6♣ 3♦ 8♣ 10♣
+6♠ 9♣ 2♥ 6♥
+";
+
+#[test]
+fn deals_same_cards_for_same_seed() {
+    assert_eq!(Deal::Seeded(5).deck(), Deal::Seeded(5).deck());
+
+    let game = Game::new(Rules::default(), Deal::Seeded(5));
+    let same_game = Game::new(Rules::default(), Deal::Seeded(5));
+    assert_eq!(game.stacks, same_game.stacks);
+    assert_eq!(game.deck, same_game.deck);
+}
+
+#[test]
+fn deals_different_cards_for_different_seeds() {
+    let decks: Vec<_> = (0..20).map(|seed| Deal::Seeded(seed).deck()).collect();
+
+    for (i, deck) in decks.iter().enumerate() {
+        assert!(
+            decks[i + 1..].iter().all(|other_deck| other_deck != deck),
+            "Seed {i} deals the same cards as another seed"
+        );
+    }
+}
+
+#[test]
+fn deals_every_card_once() {
+    for deal in [Deal::Seeded(5), Deal::Microsoft(1)] {
+        let cards = deal.deck().get_cards().get_all().to_vec();
+        let distinct: HashSet<_> = cards.iter().map(ToString::to_string).collect();
+
+        assert_eq!(cards.len(), 52);
+        assert_eq!(distinct.len(), 52);
+    }
+}
+
+/// The top of the deck is its last card, the first one dealt. Cards wait
+/// face down in the deck.
+#[test]
+fn deals_microsoft_deal_like_windows() {
+    let deck = Deal::Microsoft(1).deck();
+
+    let dealt: Vec<_> = deck
+        .get_cards()
+        .get_all()
+        .iter()
+        .rev()
+        .map(ToString::to_string)
+        .collect();
+
+    let expected: Vec<_> = MICROSOFT_DEAL_1
+        .split_whitespace()
+        .map(|card| format!("#{card}"))
+        .collect();
+    assert_eq!(dealt, expected);
+}
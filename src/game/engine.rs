@@ -1,107 +1,515 @@
-use std::io::Result;
+use std::{
+    io::Result,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
 
-use super::{renderer::GameRenderer, Game, GameObject};
+use super::{
+    cursor::{Cursor, Direction},
+    moves::Move,
+    renderer::GameRenderer,
+    stats::Stats,
+    Deal, Game, GameObject, Theme,
+};
 use crossterm::event::{
-    self, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
 };
 
 pub struct GameEngine<'a> {
     renderer: GameRenderer,
     game: &'a mut Game,
+    state: GameState,
+    cursor: Cursor,
+    is_cursor_visible: bool,
+    last_click: Option<(Instant, GameObject)>,
+    hint_i: Option<usize>,
+    is_stalemate_ignored: bool,
+    stats: Stats,
+    is_stats_shown: bool,
+    is_auto_completing: bool,
+    next_tick_at: Instant,
+    last_input_at: Instant,
+    next_deal: Option<Receiver<Deal>>,
+    errors: Vec<String>,
+}
+
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+const AUTO_COMPLETE_STEP_TIME: Duration = Duration::from_millis(80);
+const TICK_TIME: Duration = Duration::from_millis(250);
+const IDLE_HINT_TIME: Duration = Duration::from_secs(30);
+
+#[derive(PartialEq)]
+enum GameState {
+    Playing,
+    Won,
+    Stuck,
 }
 
 enum GameEvent {
     MouseDrag { row: u16, column: u16 },
     MouseDown { row: u16, column: u16 },
     MouseUp { row: u16, column: u16 },
+    Resize { columns: u16, rows: u16 },
+    MoveCursor(Direction),
+    Select,
+    AutoMove,
+    AutoComplete,
+    Hint,
+    Stats,
+    Undo,
+    Redo,
     Restart,
     Exit,
+    Tick,
     None,
 }
 
-fn wait_for_event() -> GameEvent {
-    match event::read() {
-        Ok(event) => match event {
-            Event::Key(key_event) => match key_event {
-                KeyEvent {
-                    code: KeyCode::Esc,
-                    kind: KeyEventKind::Press,
-                    ..
-                } => GameEvent::Exit,
-                KeyEvent {
-                    code: KeyCode::Char('r'),
-                    kind: KeyEventKind::Press,
-                    ..
-                } => GameEvent::Restart,
+fn wait_for_event(timeout: Duration) -> Result<GameEvent> {
+    if !event::poll(timeout)? {
+        return Ok(GameEvent::None);
+    }
+
+    let event = match event::read()? {
+        Event::Key(key_event) => match key_event {
+            KeyEvent {
+                code: KeyCode::Esc,
+                kind: KeyEventKind::Press,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => GameEvent::Exit,
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                kind: KeyEventKind::Press,
+                ..
+            } => GameEvent::Restart,
+            KeyEvent {
+                code: KeyCode::Char('u'),
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('z'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => GameEvent::Undo,
+            KeyEvent {
+                code: KeyCode::Char('y'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => GameEvent::Redo,
+            KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            } => match code {
+                KeyCode::Left | KeyCode::Char('h') => GameEvent::MoveCursor(Direction::Left),
+                KeyCode::Right | KeyCode::Char('l') => GameEvent::MoveCursor(Direction::Right),
+                KeyCode::Up | KeyCode::Char('k') => GameEvent::MoveCursor(Direction::Up),
+                KeyCode::Down | KeyCode::Char('j') => GameEvent::MoveCursor(Direction::Down),
+                KeyCode::Char(' ') | KeyCode::Enter => GameEvent::Select,
+                KeyCode::Char('f') => GameEvent::AutoMove,
+                KeyCode::Char('a') => GameEvent::AutoComplete,
+                KeyCode::Char('?') => GameEvent::Hint,
+                KeyCode::Char('s') => GameEvent::Stats,
                 _ => GameEvent::None,
             },
-            Event::Mouse(mouse_event) => match mouse_event {
-                MouseEvent {
-                    row, column, kind, ..
-                } => match kind {
-                    MouseEventKind::Drag(MouseButton::Left) => GameEvent::MouseDrag { row, column },
-                    MouseEventKind::Down(MouseButton::Left) => GameEvent::MouseDown { row, column },
-                    MouseEventKind::Up(MouseButton::Left) => GameEvent::MouseUp { row, column },
-                    _ => GameEvent::None,
-                },
-            },
             _ => GameEvent::None,
         },
-        Err(error) => {
-            eprintln!("Something goes wrong: {error}");
-            GameEvent::Exit
-        }
-    }
+        Event::Mouse(MouseEvent {
+            row, column, kind, ..
+        }) => match kind {
+            MouseEventKind::Drag(MouseButton::Left) => GameEvent::MouseDrag { row, column },
+            MouseEventKind::Down(MouseButton::Left) => GameEvent::MouseDown { row, column },
+            MouseEventKind::Up(MouseButton::Left) => GameEvent::MouseUp { row, column },
+            _ => GameEvent::None,
+        },
+        Event::Resize(columns, rows) => GameEvent::Resize { columns, rows },
+        _ => GameEvent::None,
+    };
+
+    Ok(event)
 }
 
 impl<'a> GameEngine<'a> {
-    pub fn new(game: &'a mut Game) -> Self {
-        let renderer = GameRenderer::new();
-        Self { renderer, game }
+    pub fn new(game: &'a mut Game, theme: Theme) -> Self {
+        let renderer = GameRenderer::new(game.stacks.len(), theme);
+        let state = GameState::Playing;
+        Self {
+            renderer,
+            game,
+            state,
+            cursor: Cursor::new(),
+            is_cursor_visible: false,
+            last_click: None,
+            hint_i: None,
+            is_stalemate_ignored: false,
+            stats: Stats::default(),
+            is_stats_shown: false,
+            is_auto_completing: false,
+            next_tick_at: Instant::now(),
+            last_input_at: Instant::now(),
+            next_deal: None,
+            errors: Vec::new(),
+        }
     }
 
     pub fn start(&mut self) -> Result<()> {
+        self.stats = Stats::load().unwrap_or_else(|error| {
+            self.report(format!(
+                "Error loading the stats, they aren't saved until it's fixed: {error}"
+            ));
+            Stats::read_only()
+        });
         self.renderer.init()?;
 
         self.render_game();
 
         loop {
-            let event = wait_for_event();
+            let event = self.next_event();
+
+            match event {
+                GameEvent::Tick => {
+                    self.on_tick();
+                    continue;
+                }
+                GameEvent::Resize { columns, rows } => {
+                    self.renderer.resize(columns, rows);
+                    self.render_game();
+                    continue;
+                }
+                GameEvent::None => {}
+                _ => self.last_input_at = Instant::now(),
+            }
+
+            if self.is_auto_completing || self.next_deal.is_some() {
+                match event {
+                    GameEvent::Exit => break,
+                    _ => continue,
+                }
+            }
+
+            if self.is_stats_shown {
+                if !matches!(event, GameEvent::None) {
+                    self.is_stats_shown = false;
+                    self.render_game();
+                }
+                continue;
+            }
+
+            if matches!(event, GameEvent::Stats) {
+                self.is_stats_shown = true;
+                self.render_game();
+                continue;
+            }
+
+            if self.state == GameState::Won {
+                match event {
+                    GameEvent::Exit => break,
+                    GameEvent::Restart => {
+                        self.restart();
+                        self.render_game();
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if self.state == GameState::Stuck {
+                match event {
+                    GameEvent::Exit => break,
+                    GameEvent::Restart => self.restart(),
+                    GameEvent::Undo => {
+                        self.state = GameState::Playing;
+                        self.undo();
+                    }
+                    GameEvent::Select => {
+                        self.state = GameState::Playing;
+                        self.is_stalemate_ignored = true;
+                    }
+                    _ => continue,
+                }
+                self.render_game();
+                continue;
+            }
+
+            if !matches!(event, GameEvent::Hint | GameEvent::None) {
+                self.hint_i = None;
+            }
+
+            let moves_count = self.game.moves_count();
 
             let should_rerender = match event {
                 GameEvent::MouseDown { row, column } => self.on_mouse_down(row, column),
                 GameEvent::MouseUp { row, column } => self.on_mouse_up(row, column),
                 GameEvent::MouseDrag { row, column } => self.on_mouse_drag(row, column),
+                GameEvent::MoveCursor(direction) => self.on_move_cursor(direction),
+                GameEvent::Select => self.on_select(),
+                GameEvent::AutoMove => self.on_auto_move(),
+                GameEvent::AutoComplete => self.start_auto_complete(),
+                GameEvent::Hint => self.on_hint(),
+                GameEvent::Stats => false,
+                GameEvent::Undo => self.undo(),
+                GameEvent::Redo => self.redo(),
                 GameEvent::Exit => break,
                 GameEvent::Restart => {
                     self.restart();
                     true
                 }
-                GameEvent::None => false,
+                GameEvent::Tick | GameEvent::Resize { .. } | GameEvent::None => false,
             };
 
+            self.update_state(moves_count);
+
             if should_rerender {
                 self.render_game();
             }
         }
 
-        self.renderer.cleanup()?;
+        self.renderer.cleanup();
+        self.save_on_exit();
+        for error in self.errors.drain(..) {
+            eprintln!("{error}");
+        }
 
         Ok(())
     }
 
+    /// Input is waited for until the next tick is due. Ticks come at a fixed
+    /// pace even while input keeps coming, so that a long mouse drag doesn't
+    /// stop the clock or the auto complete.
+    fn next_event(&mut self) -> GameEvent {
+        let now = Instant::now();
+
+        if now >= self.next_tick_at {
+            self.next_tick_at = now + self.get_tick_time();
+            return GameEvent::Tick;
+        }
+
+        wait_for_event(self.next_tick_at - now).unwrap_or_else(|error| {
+            self.report(format!("Something goes wrong: {error}"));
+            GameEvent::Exit
+        })
+    }
+
+    fn get_tick_time(&self) -> Duration {
+        if self.is_auto_completing {
+            AUTO_COMPLETE_STEP_TIME
+        } else {
+            TICK_TIME
+        }
+    }
+
+    fn on_tick(&mut self) {
+        if self.next_deal.is_some() {
+            self.receive_next_deal();
+        } else if self.is_auto_completing {
+            self.auto_complete_step();
+            self.render_game();
+        } else if self.is_idle() {
+            self.last_input_at = Instant::now();
+            self.on_hint();
+            self.render_game();
+        } else {
+            self.renderer.render_status_bar(self.game);
+            self.present();
+        }
+    }
+
+    /// A player who hasn't touched anything for a while is shown a hint, as
+    /// if `?` was pressed.
+    fn is_idle(&self) -> bool {
+        self.state == GameState::Playing
+            && !self.is_stats_shown
+            && self.hint_i.is_none()
+            && self.renderer.get_selected_object().is_none()
+            && self.last_input_at.elapsed() >= IDLE_HINT_TIME
+    }
+
+    fn update_state(&mut self, moves_count: u32) {
+        if self.game.moves_count() > moves_count && !self.is_auto_completing {
+            self.start_auto_complete();
+        }
+
+        if self.game.is_won() {
+            self.state = GameState::Won;
+            self.record_win();
+            self.renderer.select_object(self.game, GameObject::None);
+        } else if self.game.moves_count() > moves_count
+            && !self.is_stalemate_ignored
+            && self.game.is_stuck()
+        {
+            self.state = GameState::Stuck;
+            self.renderer.select_object(self.game, GameObject::None);
+        }
+    }
+
     fn render_game(&mut self) {
-        if let Err(error) = self.renderer.render(&self.game) {
-            eprintln!("Error rendering game: {error}");
+        let cursor = self
+            .is_cursor_visible
+            .then(|| self.cursor.get_object(self.game));
+        self.renderer.set_cursor(cursor);
+
+        let hint = self.hint_i.and_then(|i| self.game.hints().get(i).copied());
+        self.renderer
+            .set_hint(hint.map(|mv| self.game.get_move_objects(mv)));
+
+        self.renderer.render(self.game);
+
+        match self.state {
+            GameState::Won => self.renderer.render_victory(self.game),
+            GameState::Stuck => self.renderer.render_stalemate(),
+            GameState::Playing => {}
+        }
+
+        if self.is_stats_shown {
+            let stats = self.stats.get(&self.game.rules);
+            self.renderer.render_stats(self.game, &stats);
+        }
+
+        if self.next_deal.is_some() {
+            self.renderer.render_dealing();
+        }
+
+        self.present();
+    }
+
+    /// A game left for a new deal has been counted and recorded already,
+    /// there's nothing to resume.
+    fn save_on_exit(&mut self) {
+        if self.next_deal.is_some() {
+            if let Err(error) = Game::delete_save() {
+                self.report(format!("Error saving the game: {error}"));
+            }
+            return;
+        }
+
+        self.record_loss();
+        if let Err(error) = self.game.save_record() {
+            self.report(format!("Error recording the game: {error}"));
+        }
+        if let Err(error) = self.game.save_unfinished() {
+            self.report(format!("Error saving the game: {error}"));
+        }
+    }
+
+    /// Errors can't be printed while the game takes over the terminal, they
+    /// are kept to be printed once it's given back. One that keeps coming up,
+    /// like every frame failing to draw, is kept once.
+    fn report(&mut self, error: String) {
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+
+    fn present(&mut self) {
+        if let Err(error) = self.renderer.present() {
+            self.report(format!("Error rendering game: {error}"));
         }
     }
 
+    fn record_win(&mut self) {
+        if let Err(error) = self.stats.record_win(self.game) {
+            self.report(format!("Error saving the stats: {error}"));
+        }
+        self.game.is_counted_in_stats = true;
+    }
+
+    /// Leaving a started game or dealing another one counts as a loss, once
+    /// per game even if it's saved and resumed.
+    fn record_loss(&mut self) {
+        if self.state == GameState::Won
+            || self.game.moves_count() == 0
+            || self.game.is_counted_in_stats
+        {
+            return;
+        }
+
+        if let Err(error) = self.stats.record_loss(self.game) {
+            self.report(format!("Error saving the stats: {error}"));
+        }
+        self.game.is_counted_in_stats = true;
+    }
+
     fn restart(&mut self) {
-        self.game.restart();
+        self.record_loss();
+        if let Err(error) = self.game.save_record() {
+            self.report(format!("Error recording the game: {error}"));
+        }
+
+        self.renderer.select_object(self.game, GameObject::None);
+        self.is_auto_completing = false;
+
+        let like = self.game.get_deal_like_last();
+        match self.game.winnable_deals {
+            Some(winnable_deals) => {
+                let rules = self.game.rules;
+                let (sender, receiver) = mpsc::channel();
+                thread::spawn(move || sender.send(winnable_deals.find_deal(rules, like)));
+                self.next_deal = Some(receiver);
+            }
+            None => self.deal(like.random_like()),
+        }
+    }
+
+    /// The solver looks for a winnable deal on its own thread, so that the
+    /// game keeps up with input and resizes meanwhile. A solver that failed
+    /// leaves a deal it didn't check.
+    fn receive_next_deal(&mut self) {
+        let Some(next_deal) = &self.next_deal else {
+            return;
+        };
+
+        let deal = match next_deal.try_recv() {
+            Ok(deal) => deal,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => self.game.get_deal_like_last().random_like(),
+        };
+
+        self.next_deal = None;
+        self.deal(deal);
+        self.render_game();
+    }
+
+    fn deal(&mut self, deal: Deal) {
+        if let Err(error) = self.game.restart(deal) {
+            self.report(format!("Error saving the bankroll: {error}"));
+        }
+        self.state = GameState::Playing;
+        self.is_stalemate_ignored = false;
+    }
+
+    fn undo(&mut self) -> bool {
+        self.is_stalemate_ignored = false;
+        self.renderer.select_object(self.game, GameObject::None);
+        self.game.undo()
+    }
+
+    fn redo(&mut self) -> bool {
+        self.renderer.select_object(self.game, GameObject::None);
+        self.game.redo()
     }
 
     fn on_mouse_down(&mut self, row: u16, column: u16) -> bool {
-        let object = self.renderer.get_object_at(&self.game, row, column);
+        let object = self.renderer.get_object_at(self.game, row, column);
+
+        if self.is_double_click_on(object) {
+            self.last_click = None;
+            self.renderer.select_object(self.game, GameObject::None);
+            return self.game.auto_move(object);
+        }
+        self.last_click = Some((Instant::now(), object));
+
         match object {
             GameObject::Deck => {
                 self.on_click_on_deck();
@@ -110,16 +518,13 @@ impl<'a> GameEngine<'a> {
             | GameObject::LastCardOfStack(_)
             | GameObject::SuitStack(_)
             | GameObject::CardOfStack { .. } => {
-                self.renderer.select_object(&self.game, object);
+                self.renderer.select_object(self.game, object);
                 self.renderer.set_selected_object_position(row, column);
             }
             _ => {}
         }
 
-        match object {
-            GameObject::Deck => true,
-            _ => false,
-        }
+        matches!(object, GameObject::Deck)
     }
 
     fn on_mouse_up(&mut self, row: u16, column: u16) -> bool {
@@ -128,38 +533,54 @@ impl<'a> GameEngine<'a> {
             return false;
         }
 
-        let target_object = self.renderer.get_object_at(&self.game, row, column);
+        let target_object = self.renderer.get_object_at(self.game, row, column);
 
-        match (selected_object, target_object) {
-            (GameObject::Pile, GameObject::SuitStack(i)) => {
-                self.game.move_card_from_pile_to_suit_stack(i as usize);
-            }
+        self.drop_selected_object_on(target_object)
+    }
+
+    fn drop_selected_object_on(&mut self, target_object: GameObject) -> bool {
+        let selected_object = self.renderer.get_selected_object();
+
+        let mv = match (selected_object, target_object) {
+            (GameObject::Pile, GameObject::SuitStack(i)) => Some(Move::PileToSuitStack(i as usize)),
             (GameObject::Pile, GameObject::LastCardOfStack(i)) => {
-                self.game.move_card_from_pile_to_stack(i as usize);
+                Some(Move::PileToStack(i as usize))
             }
             (GameObject::SuitStack(i), GameObject::LastCardOfStack(j)) => {
-                self.game
-                    .move_card_from_suit_stack_to_stack(i as usize, j as usize);
+                Some(Move::SuitStackToStack {
+                    from: i as usize,
+                    into: j as usize,
+                })
             }
             (GameObject::LastCardOfStack(i), GameObject::SuitStack(j)) => {
-                self.game
-                    .move_card_from_stack_to_suit_stack(i as usize, j as usize);
+                Some(Move::StackToSuitStack {
+                    from: i as usize,
+                    into: j as usize,
+                })
             }
             (GameObject::LastCardOfStack(i), GameObject::LastCardOfStack(j)) => {
-                self.game
-                    .move_card_from_stack_to_stack(i as usize, j as usize);
+                Some(Move::StackToStack {
+                    from: i as usize,
+                    into: j as usize,
+                    count: 1,
+                })
             }
             (GameObject::CardOfStack { card_i, stack_i }, GameObject::LastCardOfStack(j)) => {
-                self.game.move_cards_from_stack_to_stack(
-                    stack_i as usize,
-                    j as usize,
-                    card_i as usize,
-                );
+                let stack_len = self.game.stacks[stack_i as usize].len();
+                Some(Move::StackToStack {
+                    from: stack_i as usize,
+                    into: j as usize,
+                    count: stack_len - card_i as usize,
+                })
             }
-            _ => {}
+            _ => None,
+        };
+
+        if let Some(mv) = mv {
+            self.game.make_move(mv);
         }
 
-        self.renderer.select_object(&self.game, GameObject::None);
+        self.renderer.select_object(self.game, GameObject::None);
 
         true
     }
@@ -170,7 +591,107 @@ impl<'a> GameEngine<'a> {
         !self.renderer.get_selected_object().is_none()
     }
 
+    fn on_move_cursor(&mut self, direction: Direction) -> bool {
+        if self.is_cursor_visible {
+            self.cursor.move_to(self.game, direction);
+        }
+        self.is_cursor_visible = true;
+
+        if !self.renderer.get_selected_object().is_none() {
+            let object = self.cursor.get_object(self.game);
+            self.renderer.lift_selected_object_over(self.game, object);
+        }
+
+        true
+    }
+
+    fn on_select(&mut self) -> bool {
+        self.is_cursor_visible = true;
+        let object = self.cursor.get_object(self.game);
+
+        if !self.renderer.get_selected_object().is_none() {
+            return self.drop_selected_object_on(object);
+        }
+
+        match object {
+            GameObject::Deck => self.on_click_on_deck(),
+            _ => {
+                self.renderer.select_object(self.game, object);
+                self.renderer.lift_selected_object_over(self.game, object);
+            }
+        }
+
+        true
+    }
+
+    /// Every press shows the next hint, going back to the best one after the
+    /// last.
+    fn on_hint(&mut self) -> bool {
+        let hints_count = self.game.hints().len();
+
+        self.hint_i = match self.hint_i {
+            _ if hints_count == 0 => None,
+            Some(i) => Some((i + 1) % hints_count),
+            None => Some(0),
+        };
+
+        true
+    }
+
+    /// The cards are sent to the suit stacks one per tick, so that they can
+    /// be seen going.
+    fn start_auto_complete(&mut self) -> bool {
+        if !self.game.can_auto_complete() {
+            return false;
+        }
+
+        self.renderer.select_object(self.game, GameObject::None);
+        self.is_auto_completing = true;
+        self.next_tick_at = Instant::now() + AUTO_COMPLETE_STEP_TIME;
+
+        true
+    }
+
+    fn auto_complete_step(&mut self) {
+        let moves_count = self.game.moves_count();
+
+        self.is_auto_completing = self.game.auto_complete_step() && self.game.can_auto_complete();
+
+        self.update_state(moves_count);
+    }
+
+    fn on_auto_move(&mut self) -> bool {
+        let selected_object = self.renderer.get_selected_object();
+
+        let object = if selected_object.is_none() {
+            self.cursor.get_object(self.game)
+        } else {
+            self.renderer.select_object(self.game, GameObject::None);
+            selected_object
+        };
+
+        self.game.auto_move(object);
+
+        true
+    }
+
+    fn is_double_click_on(&self, object: GameObject) -> bool {
+        let is_movable = matches!(
+            object,
+            GameObject::Pile | GameObject::LastCardOfStack(_) | GameObject::CardOfStack { .. }
+        );
+
+        match self.last_click {
+            Some((time, last_object)) => {
+                is_movable && last_object == object && time.elapsed() <= DOUBLE_CLICK_TIME
+            }
+            None => false,
+        }
+    }
+
+    /// A card held from the pile would be drawn over by the new ones.
     fn on_click_on_deck(&mut self) {
-        self.game.move_cards_from_deck_to_pile();
+        self.renderer.select_object(self.game, GameObject::None);
+        self.game.make_move(Move::Draw);
     }
 }
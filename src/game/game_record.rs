@@ -0,0 +1,123 @@
+use std::{
+    fs, io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::storage;
+
+use super::{
+    moves::Move,
+    save::{get_deal_line, get_rules_line, next_line, parse_deal, parse_rules, parse_value},
+    Game,
+};
+
+const RECORDS_DIR: &str = "records";
+const RECORD_HEADER: &str = "consolitaire record";
+const RECORD_VERSION: u32 = 1;
+const MOVES_LABEL: &str = "moves";
+
+/// A played game: the position it started from and the moves made since, in
+/// the notation of [`Move`]'s `Display`. Written as text, the rules and the
+/// deal first, then the starting board and the moves on one line.
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    start: Game,
+    moves: Vec<Move>,
+}
+
+impl GameRecord {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|error| error.to_string())?;
+        Self::from_text(&text)
+    }
+
+    /// A fresh copy of the starting position.
+    pub fn start(&self) -> Game {
+        self.start.clone()
+    }
+
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    fn to_text(&self) -> String {
+        let moves = self.moves.iter().map(Move::to_string);
+
+        format!(
+            "{RECORD_HEADER} {RECORD_VERSION}\n{}\n{}\n{}{}\n",
+            get_rules_line(&self.start.rules),
+            get_deal_line(self.start.deal),
+            self.start,
+            moves.fold(MOVES_LABEL.to_string(), |line, mv| line + " " + &mv),
+        )
+    }
+
+    fn from_text(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+
+        let version: u32 = parse_value("version", next_line(&mut lines, RECORD_HEADER)?)?;
+        if version != RECORD_VERSION {
+            return Err(format!("Unsupported record version {version}"));
+        }
+
+        let rules = parse_rules(next_line(&mut lines, "rules")?)?;
+        let deal = parse_deal(next_line(&mut lines, "deal")?)?;
+
+        let rest: Vec<&str> = lines.filter(|line| !line.trim().is_empty()).collect();
+        let Some((moves_line, board)) = rest.split_last() else {
+            return Err("Expected the board and the moves, found the end of the file".to_string());
+        };
+
+        let mut start = Game::from_board(rules, &board.join("\n"))?;
+        start.deal = deal;
+
+        let moves = moves_line
+            .strip_prefix(MOVES_LABEL)
+            .ok_or_else(|| format!("Expected '{MOVES_LABEL}', found '{moves_line}'"))?
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<Move>, _>>()?;
+
+        let mut game = start.clone();
+        for (i, &mv) in moves.iter().enumerate() {
+            if !game.make_move(mv) {
+                return Err(format!("Move {} '{mv}' can't be made", i + 1));
+            }
+        }
+
+        Ok(Self { start, moves })
+    }
+}
+
+impl Game {
+    /// The game as played so far, moves taken back with undo left out.
+    pub fn to_record(&self) -> GameRecord {
+        let mut start = self.clone();
+        while start.undo() {}
+
+        GameRecord {
+            start,
+            moves: self
+                .history
+                .get_done()
+                .iter()
+                .map(|record| record.mv)
+                .collect(),
+        }
+    }
+
+    /// Every game with a move made is kept in its own file, named after the
+    /// time it was written in milliseconds.
+    pub fn save_record(&self) -> io::Result<()> {
+        if self.moves_count == 0 {
+            return Ok(());
+        }
+
+        let milliseconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis());
+        let name = format!("{RECORDS_DIR}/{milliseconds}.txt");
+
+        storage::write(&name, &self.to_record().to_text())
+    }
+}
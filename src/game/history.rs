@@ -0,0 +1,57 @@
+use super::{moves::Move, scoring::Scoring};
+
+/// Everything needed to take a move back: the move itself, whether it
+/// turned a hidden card face up, for draws, how many cards were drawn
+/// (zero means the pile was recycled into the deck) and the scoring as it
+/// was before the move.
+#[derive(Debug, Clone, Copy)]
+pub struct Record {
+    pub mv: Move,
+    pub revealed: bool,
+    pub drawn: usize,
+    pub scoring: Scoring,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct History {
+    done: Vec<Record>,
+    undone: Vec<Move>,
+}
+
+impl History {
+    pub fn new(done: Vec<Record>, undone: Vec<Move>) -> Self {
+        Self { done, undone }
+    }
+
+    pub fn get_done(&self) -> &[Record] {
+        &self.done
+    }
+
+    pub fn get_undone(&self) -> &[Move] {
+        &self.undone
+    }
+
+    pub fn push(&mut self, record: Record) {
+        self.done.push(record);
+        self.undone.clear();
+    }
+
+    pub fn push_redone(&mut self, record: Record) {
+        self.done.push(record);
+    }
+
+    pub fn pop_done(&mut self) -> Option<Record> {
+        let record = self.done.pop()?;
+        self.undone.push(record.mv);
+        Some(record)
+    }
+
+    pub fn pop_undone(&mut self) -> Option<Move> {
+        self.undone.pop()
+    }
+
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+}
@@ -0,0 +1,282 @@
+use super::{
+    card_collections::{Deck, Pile, Stack},
+    CARDS_IN_SUIT, SUIT_STACKS_COUNT,
+};
+
+const PADDING_X: u16 = 2;
+const PADDING_Y: u16 = 1;
+/// Card sizes as columns and rows, from the one tried first.
+const CARD_SIZES: [(u16, u16); 2] = [(7, 5), (5, 3)];
+const MAX_GAP_BETWEEN_STACKS: u16 = 3;
+const MIN_GAP_BETWEEN_STACKS: u16 = 1;
+const PILE_CARDS_SPREAD: u16 = 3;
+const STACK_CARD_Y_OFFSET: u16 = 2;
+const EMPTY_DECK_Y: u16 = 0;
+const PILE_LAST_CARD_Y: u16 = 0;
+const FIRST_SUIT_STACK_X: u16 = 0;
+const FIRST_SUIT_STACK_Y: u16 = 0;
+const FIRST_STACK_X: u16 = FIRST_SUIT_STACK_X;
+
+/// Where everything is on the screen. Positions are given relative to the
+/// top left corner of the table, the padding around the table is added only
+/// when drawing.
+#[derive(Debug, Clone, Copy)]
+pub struct Layout {
+    pub padding_x: u16,
+    pub padding_y: u16,
+    pub table_cols: u16,
+    pub table_rows: u16,
+    pub card_cols: u16,
+    pub card_rows: u16,
+    gap_between_stacks: u16,
+    stack_card_x_offset: u16,
+}
+
+impl Layout {
+    /// The largest cards and the widest gaps that fit the terminal, the
+    /// table centered horizontally and filling it vertically. `None` when
+    /// even the smallest layout doesn't fit.
+    pub fn new(columns: u16, rows: u16, stacks_count: usize) -> Option<Self> {
+        let table_rows = rows.checked_sub(2 * PADDING_Y)?;
+
+        let layout = get_layouts(stacks_count as u16).find(|layout| {
+            layout.table_cols + 2 * PADDING_X <= columns && layout.table_rows <= table_rows
+        })?;
+
+        Some(Self {
+            padding_x: (columns - layout.table_cols) / 2,
+            table_rows,
+            ..layout
+        })
+    }
+
+    /// The layout with the smallest cards and gaps, as large as the table
+    /// it needs.
+    pub fn smallest(stacks_count: usize) -> Self {
+        get_layouts(stacks_count as u16)
+            .last()
+            .expect("there is at least one card size")
+    }
+
+    /// The terminal size this layout needs, as columns and rows.
+    pub fn get_screen_size(&self) -> (u16, u16) {
+        (
+            self.table_cols + 2 * self.padding_x,
+            self.table_rows + 2 * self.padding_y,
+        )
+    }
+
+    pub fn get_status_bar_y(&self) -> u16 {
+        self.table_rows
+    }
+
+    pub fn get_replay_bar_y(&self) -> u16 {
+        self.table_rows - 1
+    }
+
+    pub fn get_pile_cards_spread(&self) -> u16 {
+        PILE_CARDS_SPREAD
+    }
+
+    pub fn get_deck_position(&self, deck: &Deck) -> (u16, u16) {
+        let x = self.get_empty_deck_x();
+        let y = EMPTY_DECK_Y;
+
+        if deck.len() <= 1 {
+            (x, y)
+        } else {
+            (x - 1, y)
+        }
+    }
+
+    pub fn get_pile_last_card_position(&self) -> (u16, u16) {
+        let x = self.get_empty_deck_x() - 1 - self.get_gap_between_pile_and_deck() - self.card_cols;
+
+        (x, PILE_LAST_CARD_Y)
+    }
+
+    pub fn get_pile_position(&self, pile: &Pile) -> (u16, u16) {
+        let (mut x, y) = self.get_pile_last_card_position();
+
+        let all_cards_count = pile.len() as u16;
+        let visible_cards_count = pile.get_visible_cards().len() as u16;
+
+        x -= visible_cards_count.saturating_sub(1) * PILE_CARDS_SPREAD;
+
+        if visible_cards_count < all_cards_count {
+            x -= 1;
+        }
+
+        (x, y)
+    }
+
+    pub fn get_suit_stack_position(&self, i: u16) -> (u16, u16) {
+        let x = FIRST_SUIT_STACK_X + i * (self.card_cols + self.gap_between_stacks);
+        let y = FIRST_SUIT_STACK_Y;
+
+        (x, y)
+    }
+
+    pub fn get_stack_position(&self, i: u16) -> (u16, u16) {
+        let x = FIRST_STACK_X + i * (self.card_cols + self.gap_between_stacks);
+        let y = self.get_first_stack_y();
+
+        (x, y)
+    }
+
+    /// How far each face up card of a stack is from the one below it.
+    pub fn get_stack_card_offset(&self) -> (u16, u16) {
+        (self.stack_card_x_offset, STACK_CARD_Y_OFFSET)
+    }
+
+    pub fn get_stack_card_position(&self, i: u16, stack: &Stack, card_i: u16) -> (u16, u16) {
+        let (x, y) = self.get_stack_position(i);
+        let hidden_card_y_offset = self.get_hidden_card_y_offset(stack);
+
+        let y_offset: u16 = stack
+            .get_all()
+            .iter()
+            .take(card_i as usize)
+            .map(|card| {
+                if card.hidden {
+                    hidden_card_y_offset
+                } else {
+                    STACK_CARD_Y_OFFSET
+                }
+            })
+            .sum();
+
+        (x + card_i * self.stack_card_x_offset, y + y_offset)
+    }
+
+    pub fn get_stack_last_card_position(&self, i: u16, stack: &Stack) -> (u16, u16) {
+        let last_card_i = (stack.len() as u16).saturating_sub(1);
+
+        self.get_stack_card_position(i, stack, last_card_i)
+    }
+
+    pub fn get_index_of_card_in_stack_at(
+        &self,
+        i: u16,
+        stack: &Stack,
+        x: u16,
+        y: u16,
+    ) -> Option<u16> {
+        for (card_i, card) in stack.get_all().iter().enumerate().rev() {
+            if card.hidden {
+                return None;
+            }
+
+            let (card_x, card_y) = self.get_stack_card_position(i, stack, card_i as u16);
+            if self.is_point_inside_card(x, y, card_x, card_y) {
+                return Some(card_i as u16);
+            }
+        }
+
+        None
+    }
+
+    /// Takes a point on the screen, unlike the rest.
+    pub fn is_point_outside_of_table(&self, x: u16, y: u16) -> bool {
+        x < self.padding_x
+            || y < self.padding_y
+            || x >= self.padding_x + self.table_cols
+            || y >= self.padding_y + self.table_rows
+    }
+
+    pub fn is_point_inside_card(&self, px: u16, py: u16, cx: u16, cy: u16) -> bool {
+        px >= cx && px < cx + self.card_cols && py >= cy && py < cy + self.card_rows
+    }
+
+    /// Stacks too long to fit the table have their face down cards
+    /// squeezed, down to only the top edge of each, and then stacked right
+    /// on each other. Face up cards always keep their value in sight.
+    fn get_hidden_card_y_offset(&self, stack: &Stack) -> u16 {
+        let cards = stack.get_all();
+        let hidden_cards_count = cards.iter().filter(|card| card.hidden).count() as u16;
+        let shown_offsets_count = (cards.len() as u16)
+            .saturating_sub(hidden_cards_count)
+            .saturating_sub(1);
+        let shown_cards_rows = shown_offsets_count * STACK_CARD_Y_OFFSET + self.card_rows;
+
+        (0..=STACK_CARD_Y_OFFSET)
+            .rev()
+            .find(|y_offset| {
+                self.get_first_stack_y() + hidden_cards_count * y_offset + shown_cards_rows
+                    <= self.table_rows
+            })
+            .unwrap_or(0)
+    }
+
+    fn get_first_stack_y(&self) -> u16 {
+        FIRST_SUIT_STACK_Y + self.card_rows + 1
+    }
+
+    fn get_empty_deck_x(&self) -> u16 {
+        self.table_cols - self.card_cols
+    }
+
+    fn get_gap_between_pile_and_deck(&self) -> u16 {
+        2 * self.gap_between_stacks
+    }
+}
+
+/// Every card size and gap, from the largest to the smallest, each with the
+/// table it needs when the deal's longest stack is laid out.
+fn get_layouts(stacks_count: u16) -> impl Iterator<Item = Layout> {
+    CARD_SIZES
+        .into_iter()
+        .flat_map(move |(card_cols, card_rows)| {
+            (MIN_GAP_BETWEEN_STACKS..=MAX_GAP_BETWEEN_STACKS)
+                .rev()
+                .map(move |gap_between_stacks| {
+                    get_layout(stacks_count, card_cols, card_rows, gap_between_stacks)
+                })
+        })
+}
+
+/// Small cards are stacked straight down, there isn't room to spread them.
+/// The table is large enough for the longest stack a deal can grow: the
+/// face down cards of the last stack squeezed, then a full suit face up.
+fn get_layout(
+    stacks_count: u16,
+    card_cols: u16,
+    card_rows: u16,
+    gap_between_stacks: u16,
+) -> Layout {
+    let stack_card_x_offset = if card_rows >= 5 { 1 } else { 0 };
+
+    let mut layout = Layout {
+        padding_x: PADDING_X,
+        padding_y: PADDING_Y,
+        table_cols: 0,
+        table_rows: 0,
+        card_cols,
+        card_rows,
+        gap_between_stacks,
+        stack_card_x_offset,
+    };
+
+    let longest_stack_hidden_count = stacks_count.saturating_sub(2);
+    let longest_stack_shown_count = CARDS_IN_SUIT as u16;
+
+    let stacks_cols = FIRST_STACK_X + stacks_count * (card_cols + gap_between_stacks)
+        - gap_between_stacks
+        + stack_card_x_offset * (longest_stack_hidden_count + longest_stack_shown_count - 1);
+    let top_row_cols = layout.get_suit_stack_position(SUIT_STACKS_COUNT as u16).0
+        + 1
+        + 2 * PILE_CARDS_SPREAD
+        + card_cols
+        + 1
+        + layout.get_gap_between_pile_and_deck()
+        + card_cols;
+    let longest_stack_rows = layout.get_first_stack_y()
+        + longest_stack_hidden_count
+        + (longest_stack_shown_count - 1) * STACK_CARD_Y_OFFSET
+        + card_rows;
+
+    layout.table_cols = stacks_cols.max(top_row_cols);
+    layout.table_rows = longest_stack_rows + 1;
+
+    layout
+}
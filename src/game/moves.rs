@@ -0,0 +1,329 @@
+use std::{fmt, str::FromStr};
+
+use super::{
+    card::{Card, CoveringOrder, Value},
+    Game, GameObject,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Move {
+    Draw,
+    PileToStack(usize),
+    PileToSuitStack(usize),
+    StackToStack {
+        from: usize,
+        into: usize,
+        count: usize,
+    },
+    StackToSuitStack {
+        from: usize,
+        into: usize,
+    },
+    SuitStackToStack {
+        from: usize,
+        into: usize,
+    },
+}
+
+/// Compact notation: `d` draws, `w` is the pile (waste), `t1`..`t7` are
+/// the stacks (tableau) and `f1`..`f4` the suit stacks (foundations), e.g.
+/// `w-t5` or `t3-f1`. Moving several cards is written as `t3:4-t5`.
+impl fmt::Display for Move {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Move::Draw => write!(formatter, "d"),
+            Move::PileToStack(i) => write!(formatter, "w-t{}", i + 1),
+            Move::PileToSuitStack(i) => write!(formatter, "w-f{}", i + 1),
+            Move::StackToStack {
+                from,
+                into,
+                count: 1,
+            } => write!(formatter, "t{}-t{}", from + 1, into + 1),
+            Move::StackToStack { from, into, count } => {
+                write!(formatter, "t{}:{count}-t{}", from + 1, into + 1)
+            }
+            Move::StackToSuitStack { from, into } => {
+                write!(formatter, "t{}-f{}", from + 1, into + 1)
+            }
+            Move::SuitStackToStack { from, into } => {
+                write!(formatter, "f{}-t{}", from + 1, into + 1)
+            }
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text == "d" {
+            return Ok(Move::Draw);
+        }
+
+        let invalid_move = || format!("Invalid move '{text}'");
+        let (from, into) = text.split_once('-').ok_or_else(invalid_move)?;
+
+        if let Some((from, count)) = from.split_once(':') {
+            return match (get_place(from), get_place(into), count.parse()) {
+                (Some(('t', from)), Some(('t', into)), Ok(count)) if count > 0 => {
+                    Ok(Move::StackToStack { from, into, count })
+                }
+                _ => Err(invalid_move()),
+            };
+        }
+
+        match (get_place(from), get_place(into)) {
+            (Some(('w', _)), Some(('t', i))) => Ok(Move::PileToStack(i)),
+            (Some(('w', _)), Some(('f', i))) => Ok(Move::PileToSuitStack(i)),
+            (Some(('t', from)), Some(('t', into))) => Ok(Move::StackToStack {
+                from,
+                into,
+                count: 1,
+            }),
+            (Some(('t', from)), Some(('f', into))) => Ok(Move::StackToSuitStack { from, into }),
+            (Some(('f', from)), Some(('t', into))) => Ok(Move::SuitStackToStack { from, into }),
+            _ => Err(invalid_move()),
+        }
+    }
+}
+
+/// Splits `t3` into the kind of place and its zero-based index. The pile
+/// has no index.
+fn get_place(text: &str) -> Option<(char, usize)> {
+    let mut chars = text.chars();
+    let kind = chars.next()?;
+    let number = chars.as_str();
+
+    match kind {
+        'w' if number.is_empty() => Some((kind, 0)),
+        't' | 'f' => {
+            let number: usize = number.parse().ok()?;
+            Some((kind, number.checked_sub(1)?))
+        }
+        _ => None,
+    }
+}
+
+const TO_SUIT_STACK_RANK: i32 = 50;
+const REVEAL_CARD_RANK: i32 = 40;
+const PILE_TO_STACK_RANK: i32 = 30;
+const EMPTY_STACK_RANK: i32 = 20;
+const STACK_TO_STACK_RANK: i32 = 10;
+const SUIT_STACK_TO_STACK_RANK: i32 = 5;
+const DRAW_RANK: i32 = 1;
+const USELESS_RANK: i32 = 0;
+
+impl Game {
+    /// Every move the rules allow right now, moves of face up runs of any
+    /// length included.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+
+        if !self.deck.is_empty() || self.can_recycle() {
+            moves.push(Move::Draw);
+        }
+
+        let pile_card = self.pile.get_visible_cards().last();
+        for i in self.suit_stacks_accepting(pile_card) {
+            moves.push(Move::PileToSuitStack(i));
+        }
+        for i in self.stacks_accepting(pile_card, None) {
+            moves.push(Move::PileToStack(i));
+        }
+
+        for (from, stack) in self.stacks.iter().enumerate() {
+            for into in self.suit_stacks_accepting(stack.last()) {
+                moves.push(Move::StackToSuitStack { from, into });
+            }
+
+            let cards = stack.get_all();
+            for (card_i, card) in cards.iter().enumerate().filter(|(_, card)| !card.hidden) {
+                for into in self.stacks_accepting(Some(card), Some(from)) {
+                    let count = cards.len() - card_i;
+                    moves.push(Move::StackToStack { from, into, count });
+                }
+            }
+        }
+
+        for (from, stack) in self.suit_stacks.iter().enumerate() {
+            for into in self.stacks_accepting(stack.last(), None) {
+                moves.push(Move::SuitStackToStack { from, into });
+            }
+        }
+
+        moves
+    }
+
+    /// Legal moves worth suggesting, the most promising first. Moves that
+    /// change nothing, like a king travelling between empty stacks, are left
+    /// out.
+    pub fn hints(&self) -> Vec<Move> {
+        let mut hints: Vec<(i32, Move)> = self
+            .legal_moves()
+            .into_iter()
+            .map(|mv| (self.rank_move(mv), mv))
+            .filter(|(rank, _)| *rank > USELESS_RANK)
+            .collect();
+
+        hints.sort_by_key(|(rank, _)| -rank);
+        hints.into_iter().map(|(_, mv)| mv).collect()
+    }
+
+    /// Whether the game is lost: no move makes progress now, nor with any
+    /// card that can still be drawn from the deck. Moves from suit stacks
+    /// back to stacks and moves that just shuffle cards around don't count as
+    /// progress.
+    pub fn is_stuck(&self) -> bool {
+        if self.is_won() {
+            return false;
+        }
+
+        let mut game = self.clone();
+        let max_draws = 2 * (game.deck.len() + game.pile.len()) + 2;
+
+        for _ in 0..max_draws {
+            if game.has_progress_move() {
+                return false;
+            }
+            if game.apply_move(Move::Draw).is_none() {
+                return true;
+            }
+        }
+
+        true
+    }
+
+    fn has_progress_move(&self) -> bool {
+        self.hints()
+            .iter()
+            .any(|mv| !matches!(mv, Move::Draw | Move::SuitStackToStack { .. }))
+    }
+
+    /// The objects a move takes a card from and puts it on.
+    pub(super) fn get_move_objects(&self, mv: Move) -> (GameObject, GameObject) {
+        match mv {
+            Move::Draw => (GameObject::Deck, GameObject::Pile),
+            Move::PileToStack(i) => (GameObject::Pile, GameObject::LastCardOfStack(i as u16)),
+            Move::PileToSuitStack(i) => (GameObject::Pile, GameObject::SuitStack(i as u16)),
+            Move::StackToStack { from, into, count } => {
+                let card_i = self.stacks[from].len() - count;
+                let source = if count == 1 {
+                    GameObject::LastCardOfStack(from as u16)
+                } else {
+                    GameObject::CardOfStack {
+                        stack_i: from as u16,
+                        card_i: card_i as u16,
+                    }
+                };
+                (source, GameObject::LastCardOfStack(into as u16))
+            }
+            Move::StackToSuitStack { from, into } => (
+                GameObject::LastCardOfStack(from as u16),
+                GameObject::SuitStack(into as u16),
+            ),
+            Move::SuitStackToStack { from, into } => (
+                GameObject::SuitStack(from as u16),
+                GameObject::LastCardOfStack(into as u16),
+            ),
+        }
+    }
+
+    pub(super) fn rank_move(&self, mv: Move) -> i32 {
+        match mv {
+            Move::Draw => DRAW_RANK,
+            Move::PileToSuitStack(_) => TO_SUIT_STACK_RANK,
+            Move::StackToSuitStack { from, .. } => {
+                TO_SUIT_STACK_RANK + self.rank_uncovering(from, 1)
+            }
+            Move::PileToStack(_) => PILE_TO_STACK_RANK,
+            Move::StackToStack { from, into, count }
+                if self.stacks[into].is_empty() && self.stacks[from].len() == count =>
+            {
+                USELESS_RANK
+            }
+            Move::StackToStack { from, count, .. } => match self.rank_uncovering(from, count) {
+                USELESS_RANK if self.uncovers_playable_card(from, count) => STACK_TO_STACK_RANK,
+                rank => rank,
+            },
+            Move::SuitStackToStack { .. } => SUIT_STACK_TO_STACK_RANK,
+        }
+    }
+
+    /// What is gained by taking `count` cards from a stack: turning a hidden
+    /// card face up (the more hidden cards are left, the better) or emptying
+    /// the stack while there is a king to put there.
+    fn rank_uncovering(&self, from: usize, count: usize) -> i32 {
+        let cards = self.stacks[from].get_all();
+
+        match cards.len().checked_sub(count + 1) {
+            Some(card_i) if cards[card_i].hidden => REVEAL_CARD_RANK + card_i as i32,
+            Some(_) => USELESS_RANK,
+            None if self.has_king_to_put_on_empty_stack(from) => EMPTY_STACK_RANK,
+            None => USELESS_RANK,
+        }
+    }
+
+    /// Whether moving `count` cards away leaves a card that can go to a suit
+    /// stack or take the card from the pile.
+    fn uncovers_playable_card(&self, from: usize, count: usize) -> bool {
+        let cards = self.stacks[from].get_all();
+        let Some(card) = cards.len().checked_sub(count + 1).map(|i| &cards[i]) else {
+            return false;
+        };
+
+        let pile_card = self.pile.get_visible_cards().last();
+
+        !self.suit_stacks_accepting(Some(card)).is_empty()
+            || Card::can_one_be_covered_with_another(
+                Some(card),
+                pile_card,
+                CoveringOrder::Descending,
+            )
+    }
+
+    fn has_king_to_put_on_empty_stack(&self, except: usize) -> bool {
+        let is_king = |card: &Card| !card.hidden && card.value == Value::King;
+
+        self.pile.get_visible_cards().last().is_some_and(is_king)
+            || self.stacks.iter().enumerate().any(|(i, stack)| {
+                i != except
+                    && stack
+                        .get_all()
+                        .iter()
+                        .enumerate()
+                        .any(|(card_i, card)| card_i > 0 && is_king(card))
+            })
+    }
+
+    pub(super) fn suit_stacks_accepting(&self, card: Option<&Card>) -> Vec<usize> {
+        (0..self.suit_stacks.len())
+            .filter(|&i| {
+                Card::can_one_be_covered_with_another(
+                    self.suit_stacks[i].last(),
+                    card,
+                    CoveringOrder::Ascending,
+                )
+            })
+            .collect()
+    }
+
+    pub(super) fn stacks_accepting(
+        &self,
+        card: Option<&Card>,
+        except: Option<usize>,
+    ) -> Vec<usize> {
+        (0..self.stacks.len())
+            .filter(|&i| Some(i) != except)
+            .filter(|&i| {
+                Card::can_one_be_covered_with_another(
+                    self.stacks[i].last(),
+                    card,
+                    CoveringOrder::Descending,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,96 @@
+use crate::game::{Game, Rules};
+
+use super::Move;
+
+/// The jacks left are buried under cards that have nowhere to go, the
+/// deck can only be gone through again and again.
+const STUCK_BOARD: &str = "\
+foundations: K♣ K♦ 10♠ 10♥
+pile:
+deck: K♥
+1: #J♠ K♠
+2: #Q♥ #J♥ Q♠
+";
+
+/// The queen of hearts can go on the king of spades and turn the jack
+/// under it face up.
+const PLAYABLE_BOARD: &str = "\
+foundations: K♣ K♦ 10♠ 10♥
+pile:
+deck: Q♠ K♥
+1: #J♠ K♠
+2: #J♥ Q♥
+";
+
+#[test]
+fn parses_formatted_moves() {
+    let moves = [
+        Move::Draw,
+        Move::PileToStack(0),
+        Move::PileToSuitStack(3),
+        Move::StackToStack {
+            from: 2,
+            into: 6,
+            count: 1,
+        },
+        Move::StackToStack {
+            from: 6,
+            into: 0,
+            count: 12,
+        },
+        Move::StackToSuitStack { from: 9, into: 1 },
+        Move::SuitStackToStack { from: 2, into: 4 },
+    ];
+
+    for mv in moves {
+        assert_eq!(mv.to_string().parse(), Ok(mv), "{mv} doesn't parse back");
+    }
+}
+
+#[test]
+fn parses_single_card_count_as_plain_move() {
+    assert_eq!(
+        "t1:1-t2".parse(),
+        Ok(Move::StackToStack {
+            from: 0,
+            into: 1,
+            count: 1,
+        })
+    );
+}
+
+#[test]
+fn rejects_invalid_moves() {
+    let texts = [
+        "", "D", "x", "t1", "t1-", "-t1", "t0-t1", "t1-t0", "tx-t1", "w-w", "w1-t2", "d-t1",
+        "f1-f2", "t1-w", "t1:0-t2", "t1:x-t2", "t1:2-f1", "w:1-t1", "t1-t2-t3",
+    ];
+
+    for text in texts {
+        assert_eq!(
+            text.parse::<Move>(),
+            Err(format!("Invalid move '{text}'")),
+            "'{text}' is taken for a move"
+        );
+    }
+}
+
+#[test]
+fn finds_game_with_only_draws_left_stuck() {
+    let game = Game::from_board(Rules::default(), STUCK_BOARD).expect("the board is valid");
+
+    assert_eq!(game.legal_moves(), [Move::Draw]);
+    assert!(game.is_stuck());
+}
+
+#[test]
+fn finds_game_with_move_between_stacks_not_stuck() {
+    let game = Game::from_board(Rules::default(), PLAYABLE_BOARD).expect("the board is valid");
+
+    assert!(game.legal_moves().contains(&Move::StackToStack {
+        from: 1,
+        into: 0,
+        count: 1,
+    }));
+    assert!(!game.is_stuck());
+}
@@ -1,138 +1,259 @@
 use super::{
+    backend::{Backend, TerminalBackend},
     card::Card,
     card_collections::{Deck, Pile, Stack},
-    Game, GameObject,
+    layout::Layout,
+    scoring::format_dollars,
+    screen::Screen,
+    stats::RuleSetStats,
+    theme::{Palette, Theme},
+    Game, GameObject, ScoringMode,
 };
 
-use crossterm::{
-    cursor::MoveTo,
-    event::{DisableMouseCapture, EnableMouseCapture},
-    execute, queue,
-    style::{Color, Print, SetForegroundColor},
-    terminal::{size, Clear, ClearType, SetSize},
-};
-use std::{
-    io::{stdout, Result, Stdout},
-    ops::Div,
-};
+use crossterm::style::Color;
+use std::{io::Result, time::Duration};
 
-const PADDING_X: u16 = 2;
-const PADDING_Y: u16 = 1;
-const TABLE_COLS: u16 = 78;
-const TABLE_ROWS: u16 = 35;
-const CARD_COLS: u16 = 7;
-const CARD_ROWS: u16 = 5;
-const GAP_BETWEEN_STACK_CARDS: u16 = 3;
-const GAP_BETWEEN_PILE_AND_DECK: u16 = 6;
-const EMPTY_DECK_X: u16 = TABLE_COLS - CARD_COLS;
-const EMPTY_DECK_Y: u16 = 0;
-const PILE_LAST_CARD_X: u16 = EMPTY_DECK_X - 1 - GAP_BETWEEN_PILE_AND_DECK - CARD_COLS;
-const PILE_LAST_CARD_Y: u16 = 0;
-const FIRST_SUIT_STACK_X: u16 = 0;
-const FIRST_SUIT_STACK_Y: u16 = 0;
-const FIRST_STACK_X: u16 = FIRST_SUIT_STACK_X;
-const FIRST_STACK_Y: u16 = FIRST_SUIT_STACK_Y + CARD_ROWS + 1;
-const STACK_CARD_X_OFFSET: u16 = 1;
-const STACK_CARD_Y_OFFSET: u16 = 2;
-
-pub struct GameRenderer {
-    out: Stdout,
+const LIFTED_CARD_X_OFFSET: u16 = 2;
+const LIFTED_CARD_Y_OFFSET: u16 = 1;
+
+pub struct GameRenderer<B: Backend = TerminalBackend> {
+    backend: B,
+    stacks_count: usize,
+    layout: Layout,
+    palette: Palette,
+    is_too_small: bool,
+    screen: Screen,
     selected_object: GameObject,
     selected_object_row: u16,
     selected_object_column: u16,
-    console_rows: Option<u16>,
-    console_columns: Option<u16>,
+    cursor: Option<GameObject>,
+    hint: Option<(GameObject, GameObject)>,
 }
 
 impl GameRenderer {
-    pub fn new() -> Self {
+    pub fn new(stacks_count: usize, theme: Theme) -> Self {
+        Self::with_backend(TerminalBackend::new(), stacks_count, theme)
+    }
+}
+
+impl<B: Backend> GameRenderer<B> {
+    pub fn with_backend(backend: B, stacks_count: usize, theme: Theme) -> Self {
+        let layout = Layout::smallest(stacks_count);
+        let (columns, rows) = layout.get_screen_size();
+
         Self {
-            out: stdout(),
+            backend,
+            stacks_count,
+            layout,
+            palette: theme.palette(),
+            is_too_small: false,
+            screen: Screen::new(columns, rows),
             selected_object: GameObject::None,
             selected_object_column: 0,
             selected_object_row: 0,
-            console_rows: None,
-            console_columns: None,
+            cursor: None,
+            hint: None,
         }
     }
 
     pub fn init(&mut self) -> Result<()> {
-        let (columns, rows) = size()?;
-        self.console_columns = Some(columns);
-        self.console_rows = Some(rows);
+        let (columns, rows) = self.backend.size()?;
+        self.resize(columns, rows);
+
+        self.backend.start()
+    }
 
-        let new_columns = TABLE_COLS + 2 * PADDING_X;
-        let new_rows = TABLE_ROWS + 2 * PADDING_Y;
-        execute!(self.out, EnableMouseCapture, SetSize(new_columns, new_rows))
+    /// The terminal is also given back when the renderer is dropped without
+    /// a cleanup.
+    pub fn cleanup(&mut self) {
+        self.backend.stop();
     }
 
-    pub fn cleanup(&mut self) -> Result<()> {
-        match (self.console_rows, self.console_columns) {
-            (Some(rows), Some(columns)) => {
-                execute!(self.out, DisableMouseCapture, SetSize(columns, rows))
+    /// The last layout that fit is kept while the terminal is too small, so
+    /// that the selected object keeps a place to go back to.
+    pub fn resize(&mut self, columns: u16, rows: u16) {
+        self.screen.resize(columns, rows);
+
+        match Layout::new(columns, rows, self.stacks_count) {
+            Some(layout) => {
+                self.layout = layout;
+                self.is_too_small = false;
             }
-            _ => execute!(self.out, DisableMouseCapture),
+            None => self.is_too_small = true,
         }
     }
 
-    pub fn render(&mut self, game: &Game) -> Result<()> {
-        execute!(self.out, Clear(ClearType::All))?;
+    /// Frames are drawn off the terminal, this sends what changed since the
+    /// last one.
+    pub fn present(&mut self) -> Result<()> {
+        self.screen.present(&mut self.backend)
+    }
+
+    pub fn render(&mut self, game: &Game) {
+        self.screen.clear();
+
+        if self.is_too_small {
+            return self.render_too_small();
+        }
 
         for (i, stack) in game.suit_stacks.iter().enumerate() {
-            self.render_suit_stack(stack, i as u16)?;
+            self.render_suit_stack(stack, i as u16);
         }
 
-        self.render_pile(&game.pile)?;
+        self.render_pile(&game.pile);
 
-        self.render_deck(&game.deck)?;
+        self.render_deck(&game.deck);
 
         for (i, stack) in game.stacks.iter().enumerate() {
-            self.render_stack(stack, i as u16)?;
+            self.render_stack(stack, i as u16);
+        }
+
+        self.draw_status_bar(game);
+
+        self.render_selected_object(game);
+    }
+
+    pub fn render_victory(&mut self, game: &Game) {
+        let lines = [
+            "You won!".to_string(),
+            String::new(),
+            format!("Time:  {}", get_time_text(game.elapsed())),
+            format!("Moves: {}", game.moves_count()),
+            format!("Score: {}", game.score_text()),
+            String::new(),
+            "[r] Deal again   [Esc] Quit".to_string(),
+        ];
+
+        self.draw_message_box(&lines, self.palette.text);
+    }
+
+    pub fn render_stalemate(&mut self) {
+        let lines = [
+            "No more moves".to_string(),
+            String::new(),
+            "[r] New deal   [u] Undo   [Space] Keep looking".to_string(),
+        ];
+
+        self.draw_message_box(&lines, self.palette.text);
+    }
+
+    pub fn render_stats(&mut self, game: &Game, stats: &RuleSetStats) {
+        let rules = &game.rules;
+        let scoring_mode = match rules.scoring_mode {
+            ScoringMode::Standard => "standard",
+            ScoringMode::Vegas => "Vegas",
+        };
+        let best_time = stats.best_time.map(get_time_text);
+        let best_score = stats.best_score.map(|score| match rules.scoring_mode {
+            ScoringMode::Standard => score.to_string(),
+            ScoringMode::Vegas => format_dollars(score as i64),
+        });
+
+        let rows = [
+            ("Played", Some(stats.played.to_string())),
+            (
+                "Won",
+                Some(format!("{} ({}%)", stats.won, stats.win_rate())),
+            ),
+            ("Best time", best_time),
+            (
+                "Fewest moves",
+                stats.fewest_moves.map(|moves| moves.to_string()),
+            ),
+            ("Best score", best_score),
+            ("Current streak", Some(stats.current_streak.to_string())),
+            ("Longest streak", Some(stats.longest_streak.to_string())),
+        ];
+
+        let mut lines = vec![
+            format!("Draw {}, {scoring_mode} scoring", rules.draw_count),
+            String::new(),
+        ];
+        for (label, value) in rows {
+            let value = value.unwrap_or_else(|| "-".to_string());
+            lines.push(format!("{label:<16}{value:>10}"));
+        }
+        lines.push(String::new());
+        lines.push("[any key] Back".to_string());
+
+        self.draw_message_box(&lines, self.palette.text);
+    }
+
+    pub fn render_replay_bar(&mut self, text: &str) {
+        if !self.is_too_small {
+            self.screen.set_color(self.palette.text);
+            self.print_on_table(0, self.layout.get_replay_bar_y(), text);
         }
+    }
+
+    /// Draws the status bar alone, so that the clock can run without the
+    /// whole table being drawn again.
+    pub fn render_status_bar(&mut self, game: &Game) {
+        self.draw_status_bar(game);
+    }
 
-        self.render_selected_object(game)?;
+    pub fn render_dealing(&mut self) {
+        let lines = ["Looking for a winnable deal...".to_string()];
 
-        execute!(
-            self.out,
-            SetForegroundColor(Color::White),
-            MoveTo(PADDING_X, PADDING_Y + TABLE_ROWS),
-        )?;
+        self.draw_message_box(&lines, self.palette.text);
+    }
 
-        Ok(())
+    /// Drawing the table into a smaller terminal would only scramble it.
+    fn render_too_small(&mut self) {
+        let (columns, rows) = Layout::smallest(self.stacks_count).get_screen_size();
+        let lines = [
+            "The terminal is too small".to_string(),
+            format!("Make it at least {columns}×{rows}"),
+        ];
+
+        let y = (self.screen.rows() / 2).saturating_sub(1);
+        self.screen.set_color(self.palette.text);
+
+        for (i, line) in lines.iter().enumerate() {
+            let x = self
+                .screen
+                .columns()
+                .saturating_sub(line.chars().count() as u16)
+                / 2;
+            self.screen.print_at(x, y + i as u16, line);
+        }
     }
 
     pub fn get_object_at(&self, game: &Game, row: u16, column: u16) -> GameObject {
-        if is_point_outside_of_table(column, row) {
+        let layout = &self.layout;
+
+        if self.is_too_small || layout.is_point_outside_of_table(column, row) {
             return GameObject::None;
         }
 
-        let (x, y) = (column - PADDING_X, row - PADDING_Y);
+        let (x, y) = (column - layout.padding_x, row - layout.padding_y);
 
-        let (deck_x, deck_y) = get_deck_position(&game.deck);
-        if is_point_inside_card(x, y, deck_x, deck_y) {
+        let (deck_x, deck_y) = layout.get_deck_position(&game.deck);
+        if layout.is_point_inside_card(x, y, deck_x, deck_y) {
             return GameObject::Deck;
         }
 
-        if is_point_inside_card(x, y, PILE_LAST_CARD_X, PILE_LAST_CARD_Y) {
+        let (pile_x, pile_y) = layout.get_pile_last_card_position();
+        if layout.is_point_inside_card(x, y, pile_x, pile_y) {
             return GameObject::Pile;
         }
 
         for i in 0..game.suit_stacks.len() {
-            let (stack_x, stack_y) = get_suit_stack_position(i as u16);
+            let (stack_x, stack_y) = layout.get_suit_stack_position(i as u16);
 
-            if is_point_inside_card(x, y, stack_x, stack_y) {
+            if layout.is_point_inside_card(x, y, stack_x, stack_y) {
                 return GameObject::SuitStack(i as u16);
             }
         }
 
-        for i in 0..game.stacks.len() {
-            let (last_card_x, last_card_y) = get_stack_last_card_position(&game.stacks, i as u16);
+        for (i, stack) in game.stacks.iter().enumerate() {
+            let (last_card_x, last_card_y) = layout.get_stack_last_card_position(i as u16, stack);
 
-            if is_point_inside_card(x, y, last_card_x, last_card_y) {
+            if layout.is_point_inside_card(x, y, last_card_x, last_card_y) {
                 return GameObject::LastCardOfStack(i as u16);
             }
 
-            if let Some(card_i) = get_index_of_card_in_stack_at(&game.stacks, i as u16, x, y) {
+            if let Some(card_i) = layout.get_index_of_card_in_stack_at(i as u16, stack, x, y) {
                 return GameObject::CardOfStack {
                     card_i,
                     stack_i: i as u16,
@@ -152,7 +273,11 @@ impl GameRenderer {
             GameObject::Pile => !game.pile.is_empty(),
             GameObject::SuitStack(i) => !game.suit_stacks[i as usize].is_empty(),
             GameObject::LastCardOfStack(i) => !game.stacks[i as usize].is_empty(),
-            GameObject::None | GameObject::CardOfStack { .. } => true,
+            GameObject::CardOfStack { stack_i, card_i } => game.stacks[stack_i as usize]
+                .get_all()
+                .get(card_i as usize)
+                .is_some_and(|card| !card.hidden),
+            GameObject::None => true,
             _ => false,
         };
 
@@ -161,94 +286,205 @@ impl GameRenderer {
         }
     }
 
+    /// Takes a point on the screen, the object is drawn with its top left
+    /// corner there.
     pub fn set_selected_object_position(&mut self, row: u16, column: u16) {
-        self.selected_object_row = row;
-        self.selected_object_column = column;
+        self.selected_object_row = row.saturating_sub(self.layout.padding_y);
+        self.selected_object_column = column.saturating_sub(self.layout.padding_x);
     }
 
-    fn draw_hidden_card(&mut self, x0: u16, y0: u16, color: Color) -> Result<()> {
-        let (x, y) = (x0 + PADDING_X, y0 + PADDING_Y);
+    pub fn set_cursor(&mut self, cursor: Option<GameObject>) {
+        self.cursor = cursor;
+    }
 
-        self.draw_card_outline(x0, y0, color)?;
+    pub fn set_hint(&mut self, hint: Option<(GameObject, GameObject)>) {
+        self.hint = hint;
+    }
 
-        queue!(
-            self.out,
-            MoveTo(x + 1, y + 1),
-            Print("▓▓▓▓▓"),
-            MoveTo(x + 1, y + 2),
-            Print("▓▓▓▓▓"),
-            MoveTo(x + 1, y + 3),
-            Print("▓▓▓▓▓"),
-        )
+    /// Puts the selected object slightly aside of the given one, as if it was
+    /// lifted from there.
+    pub fn lift_selected_object_over(&mut self, game: &Game, object: GameObject) {
+        let (x, y) = self.get_object_position(game, object);
+        self.selected_object_row = y + LIFTED_CARD_Y_OFFSET;
+        self.selected_object_column = x + LIFTED_CARD_X_OFFSET;
     }
 
-    fn draw_card_outline(&mut self, x: u16, y: u16, color: Color) -> Result<()> {
-        let (x, y) = (x + PADDING_X, y + PADDING_Y);
-
-        queue!(
-            self.out,
-            SetForegroundColor(color),
-            MoveTo(x, y),
-            Print("╔═════╗"),
-            MoveTo(x, y + 1),
-            Print("║     ║"),
-            MoveTo(x, y + 2),
-            Print("║     ║"),
-            MoveTo(x, y + 3),
-            Print("║     ║"),
-            MoveTo(x, y + 4),
-            Print("╚═════╝"),
-        )
+    fn get_object_position(&self, game: &Game, object: GameObject) -> (u16, u16) {
+        let layout = &self.layout;
+
+        match object {
+            GameObject::Deck => layout.get_deck_position(&game.deck),
+            GameObject::Pile => layout.get_pile_last_card_position(),
+            GameObject::SuitStack(i) => layout.get_suit_stack_position(i),
+            GameObject::LastCardOfStack(i) => {
+                layout.get_stack_last_card_position(i, &game.stacks[i as usize])
+            }
+            GameObject::CardOfStack { stack_i, card_i } => {
+                layout.get_stack_card_position(stack_i, &game.stacks[stack_i as usize], card_i)
+            }
+            GameObject::None => (0, 0),
+        }
+    }
+
+    fn get_card_color(&self, object: GameObject) -> Color {
+        self.get_highlight_color(object)
+            .unwrap_or(self.palette.card)
+    }
+
+    fn get_placeholder_color(&self, object: GameObject) -> Color {
+        self.get_highlight_color(object)
+            .unwrap_or(self.palette.placeholder)
     }
 
-    fn draw_card_deshed_outline(&mut self, x: u16, y: u16, color: Color) -> Result<()> {
-        let (x, y) = (x + PADDING_X, y + PADDING_Y);
-
-        queue!(
-            self.out,
-            SetForegroundColor(color),
-            MoveTo(x, y),
-            Print("╔ ═ ═ ╗"),
-            MoveTo(x, y + 1),
-            Print("       "),
-            MoveTo(x, y + 2),
-            Print("║     ║"),
-            MoveTo(x, y + 3),
-            Print("       "),
-            MoveTo(x, y + 4),
-            Print("╚ ═ ═ ╝"),
+    fn get_highlight_color(&self, object: GameObject) -> Option<Color> {
+        let is_hinted = self
+            .hint
+            .is_some_and(|(source, target)| source == object || target == object);
+
+        if self.cursor == Some(object) {
+            Some(self.palette.cursor)
+        } else if is_hinted {
+            Some(self.palette.hint)
+        } else {
+            None
+        }
+    }
+
+    fn print_on_table(&mut self, x: u16, y: u16, text: &str) {
+        self.screen
+            .print_at(x + self.layout.padding_x, y + self.layout.padding_y, text)
+    }
+
+    fn draw_hidden_card(&mut self, x: u16, y: u16, color: Color) {
+        self.draw_card_outline(x, y, color);
+        self.screen.set_color(self.palette.card_back);
+
+        let back = "▓".repeat((self.layout.card_cols - 2) as usize);
+        for row in 1..self.layout.card_rows - 1 {
+            self.print_on_table(x + 1, y + row, &back);
+        }
+    }
+
+    fn draw_card_outline(&mut self, x: u16, y: u16, color: Color) {
+        let inner_cols = (self.layout.card_cols - 2) as usize;
+        let horizontal_line = "═".repeat(inner_cols);
+        let empty_line = " ".repeat(inner_cols);
+
+        self.screen.set_color(color);
+
+        self.print_on_table(x, y, &format!("╔{horizontal_line}╗"));
+        for row in 1..self.layout.card_rows - 1 {
+            self.print_on_table(x, y + row, &format!("║{empty_line}║"));
+        }
+        self.print_on_table(
+            x,
+            y + self.layout.card_rows - 1,
+            &format!("╚{horizontal_line}╝"),
         )
     }
 
-    fn draw_suit_card_placeholder(&mut self, x0: u16, y0: u16, color: Color) -> Result<()> {
-        let (x, y) = (x0 + PADDING_X, y0 + PADDING_Y);
+    fn draw_card_deshed_outline(&mut self, x: u16, y: u16, color: Color) {
+        let inner_cols = (self.layout.card_cols - 2) as usize;
+        let dashed_line: String = (0..inner_cols)
+            .map(|i| if i % 2 == 0 { ' ' } else { '═' })
+            .collect();
+        let empty_line = " ".repeat(inner_cols);
+
+        self.screen.set_color(color);
+
+        self.print_on_table(x, y, &format!("╔{dashed_line}╗"));
+        for row in 1..self.layout.card_rows - 1 {
+            let line = if row % 2 == 0 {
+                format!("║{empty_line}║")
+            } else {
+                format!(" {empty_line} ")
+            };
+            self.print_on_table(x, y + row, &line);
+        }
+        self.print_on_table(
+            x,
+            y + self.layout.card_rows - 1,
+            &format!("╚{dashed_line}╝"),
+        )
+    }
 
-        self.draw_card_outline(x0, y0, color)?;
+    fn draw_suit_card_placeholder(&mut self, x: u16, y: u16, color: Color) {
+        self.draw_card_outline(x, y, color);
 
-        queue!(self.out, MoveTo(x + 3, y + 2), Print("A"))
+        let (center_x, center_y) = (self.layout.card_cols / 2, self.layout.card_rows / 2);
+        self.print_on_table(x + center_x, y + center_y, "A")
     }
 
-    fn draw_card(&mut self, card: &Card, x0: u16, y0: u16, color: Color) -> Result<()> {
-        let (x, y) = (x0 + PADDING_X, y0 + PADDING_Y);
+    fn draw_message_box(&mut self, lines: &[String], color: Color) {
+        if self.is_too_small {
+            return;
+        }
+
+        let inner_cols = lines
+            .iter()
+            .map(|line| line.chars().count() as u16)
+            .max()
+            .unwrap_or(0)
+            + 4;
+        let box_cols = inner_cols + 2;
+        let box_rows = lines.len() as u16 + 4;
+        let x = self.screen.columns().saturating_sub(box_cols) / 2;
+        let y = self.layout.padding_y + self.layout.table_rows.saturating_sub(box_rows) / 2;
+
+        let horizontal_line = "═".repeat(inner_cols as usize);
+        let empty_line = " ".repeat(inner_cols as usize);
+
+        self.screen.set_color(color);
+
+        self.screen.print_at(x, y, &format!("╔{horizontal_line}╗"));
+        self.screen.print_at(x, y + 1, &format!("║{empty_line}║"));
+
+        for (i, line) in lines.iter().enumerate() {
+            self.screen.print_at(
+                x,
+                y + 2 + i as u16,
+                &format!("║{line:^width$}║", width = inner_cols as usize),
+            );
+        }
+
+        self.screen
+            .print_at(x, y + box_rows - 2, &format!("║{empty_line}║"));
+        self.screen
+            .print_at(x, y + box_rows - 1, &format!("╚{horizontal_line}╝"))
+    }
 
+    /// Large cards show the value and the suit in two corners and the suit
+    /// in the middle, small ones only in the top left corner. The outline
+    /// takes the given color, the face the color of the suit.
+    fn draw_card(&mut self, card: &Card, x: u16, y: u16, color: Color) {
         if card.hidden {
-            self.draw_hidden_card(x0, y0, color)
-        } else {
-            self.draw_card_outline(x0, y0, color)?;
+            return self.draw_hidden_card(x, y, color);
+        }
 
-            let value = card.value.to_string();
-            let suit = card.suit.to_string();
+        self.draw_card_outline(x, y, color);
 
-            queue!(
-                self.out,
-                MoveTo(x + 1, y + 1),
-                Print(format!("{value:<2} {suit} ")),
-                MoveTo(x + 1, y + 2),
-                Print(format!("  {suit}  ")),
-                MoveTo(x + 1, y + 3),
-                Print(format!(" {suit} {value:>2}")),
-            )
+        let value = card.value.to_string();
+        let suit = card.suit.to_string();
+        let (card_cols, card_rows) = (self.layout.card_cols, self.layout.card_rows);
+        self.screen
+            .set_color(self.palette.get_suit_color(&card.suit));
+
+        if card_rows < 5 {
+            return self.print_on_table(x + 1, y + 1, &format!("{value:<2}{suit}"));
         }
+
+        let inner_cols = (card_cols - 2) as usize;
+        self.print_on_table(x + 1, y + 1, &format!("{value:<2} {suit}"));
+        self.print_on_table(
+            x + 1,
+            y + card_rows / 2,
+            &format!("{suit:^width$}", width = inner_cols),
+        );
+        self.print_on_table(
+            x + 1,
+            y + card_rows - 2,
+            &format!("{suit:>width$} {value:>2}", width = inner_cols - 3),
+        )
     }
 
     fn draw_cards_in_stack(
@@ -256,88 +492,136 @@ impl GameRenderer {
         cards: &[Card],
         x0: u16,
         y0: u16,
+        (x_offset, y_offset): (u16, u16),
         color: Color,
-    ) -> Result<()> {
-        let cards_len = cards.len() as u16;
-
-        for i in 0..cards_len {
-            let card = &cards[i as usize];
-            let x = x0 + i * STACK_CARD_X_OFFSET;
-            let y = y0 + i * STACK_CARD_Y_OFFSET;
-            self.draw_card(card, x, y, color)?;
+    ) {
+        for (i, card) in cards.iter().enumerate() {
+            let x = x0 + i as u16 * x_offset;
+            let y = y0 + i as u16 * y_offset;
+            self.draw_card(card, x, y, color);
         }
-
-        Ok(())
     }
 
-    fn render_pile(&mut self, pile: &Pile) -> Result<()> {
-        let (mut x, y) = get_pile_position(pile);
+    fn render_pile(&mut self, pile: &Pile) {
+        let (mut x, y) = self.layout.get_pile_position(pile);
 
         let visible_cards = pile.get_visible_cards();
         let all_cards_count = pile.len() as u16;
         let mut visible_cards_count = visible_cards.len() as u16;
 
         if visible_cards_count < all_cards_count {
-            self.draw_card_outline(x, y, Color::White)?;
+            self.draw_card_outline(x, y, self.palette.card);
             x += 1;
         }
 
         if let GameObject::Pile = self.selected_object {
-            visible_cards_count -= 1;
+            visible_cards_count = visible_cards_count.saturating_sub(1);
+        }
+
+        if let (0, Some(color)) = (all_cards_count, self.get_highlight_color(GameObject::Pile)) {
+            return self.draw_card_deshed_outline(x, y, color);
         }
 
         for i in 0..visible_cards_count {
             let card = &visible_cards[i as usize];
-            self.draw_card(card, x, y, Color::White)?;
-            x += CARD_COLS.div(2);
+            let color = if i + 1 == visible_cards_count {
+                self.get_card_color(GameObject::Pile)
+            } else {
+                self.palette.card
+            };
+            self.draw_card(card, x, y, color);
+            x += self.layout.get_pile_cards_spread();
         }
-
-        Ok(())
     }
 
-    fn render_deck(&mut self, deck: &Deck) -> Result<()> {
-        let (x, y) = get_deck_position(deck);
+    fn render_deck(&mut self, deck: &Deck) {
+        let (x, y) = self.layout.get_deck_position(deck);
+        let color = self.get_card_color(GameObject::Deck);
 
         match deck.len() {
-            0 => self.draw_card_outline(x, y, Color::DarkGrey),
-            1 => self.draw_hidden_card(x, y, Color::White),
+            0 => self.draw_card_outline(x, y, self.get_placeholder_color(GameObject::Deck)),
+            1 => self.draw_hidden_card(x, y, color),
             _ => {
-                self.draw_hidden_card(x + 1, y, Color::White)?;
-                self.draw_hidden_card(x, y, Color::White)
+                self.draw_hidden_card(x + 1, y, self.palette.card);
+                self.draw_hidden_card(x, y, color)
             }
         }
     }
 
-    fn render_suit_stack(&mut self, stack: &Stack, i: u16) -> Result<()> {
-        let (x, y) = get_suit_stack_position(i);
+    fn render_suit_stack(&mut self, stack: &Stack, i: u16) {
+        let (x, y) = self.layout.get_suit_stack_position(i);
 
         let last_card = match self.selected_object {
             GameObject::SuitStack(j) if j == i => stack.prelast(),
             _ => stack.last(),
         };
 
+        let object = GameObject::SuitStack(i);
+
         match last_card {
-            Some(card) => self.draw_card(card, x, y, Color::White),
-            None => self.draw_suit_card_placeholder(x, y, Color::DarkGrey),
+            Some(card) => self.draw_card(card, x, y, self.get_card_color(object)),
+            None => self.draw_suit_card_placeholder(x, y, self.get_placeholder_color(object)),
         }
     }
 
-    fn render_stack(&mut self, stack: &Stack, i: u16) -> Result<()> {
-        let (x, y) = get_stack_position(i);
+    fn render_stack(&mut self, stack: &Stack, i: u16) {
+        let (x, y) = self.layout.get_stack_position(i);
 
-        self.draw_card_deshed_outline(x, y, Color::DarkGrey)?;
+        let placeholder_color = self.get_placeholder_color(GameObject::LastCardOfStack(i));
+        self.draw_card_deshed_outline(x, y, placeholder_color);
 
         let cards = stack.get_all();
         let len = match self.selected_object {
-            GameObject::CardOfStack { card_i, stack_i } if stack_i == i => card_i as usize,
-            GameObject::LastCardOfStack(stack_i) if stack_i == i => cards.len() - 1,
+            GameObject::CardOfStack { card_i, stack_i } if stack_i == i => {
+                cards.len().min(card_i as usize)
+            }
+            GameObject::LastCardOfStack(stack_i) if stack_i == i => cards.len().saturating_sub(1),
             _ => cards.len(),
         };
 
-        self.draw_cards_in_stack(&cards[..len], x, y, Color::White)
+        for (card_i, card) in cards[..len].iter().enumerate() {
+            let object = if card_i + 1 == cards.len() {
+                GameObject::LastCardOfStack(i)
+            } else {
+                GameObject::CardOfStack {
+                    stack_i: i,
+                    card_i: card_i as u16,
+                }
+            };
+
+            let (card_x, card_y) = self.layout.get_stack_card_position(i, stack, card_i as u16);
+            self.draw_card(card, card_x, card_y, self.get_card_color(object));
+        }
+    }
+
+    fn draw_status_bar(&mut self, game: &Game) {
+        if self.is_too_small {
+            return;
+        }
+
+        let mut status = format!(
+            "{}   Draw {}   Time: {}   Moves: {}   Score: {}",
+            game.deal_text(),
+            game.rules.draw_count,
+            get_time_text(game.elapsed()),
+            game.moves_count(),
+            game.score_text()
+        );
+
+        if let Some(passes) = game.passes_text() {
+            status += &format!("   Pass: {passes}");
+        }
+        if let Some(bankroll) = game.bankroll_text() {
+            status += &format!("   Bankroll: {bankroll}");
+        }
+
+        let y = self.layout.get_status_bar_y();
+        self.screen.clear_row(self.layout.padding_y + y);
+        self.screen.set_color(self.palette.status_bar);
+        self.print_on_table(0, y, &status);
     }
 
-    fn render_selected_object(&mut self, game: &Game) -> Result<()> {
+    fn render_selected_object(&mut self, game: &Game) {
         let object = self.selected_object;
         let (x, y) = (self.selected_object_column, self.selected_object_row);
 
@@ -349,99 +633,24 @@ impl GameRenderer {
         };
 
         if let Some(card) = selected_card {
-            self.draw_card(card, x, y, Color::White)?;
+            self.draw_card(card, x, y, self.palette.card);
         } else {
             if let GameObject::CardOfStack { card_i, stack_i } = object {
                 let stack = &game.stacks[stack_i as usize];
-                let cards = &stack.get_all()[card_i as usize..];
-                self.draw_cards_in_stack(cards, x, y, Color::White)?;
+                let Some(cards) = stack.get_all().get(card_i as usize..) else {
+                    return;
+                };
+                let offset = self.layout.get_stack_card_offset();
+                self.draw_cards_in_stack(cards, x, y, offset, self.palette.card);
             }
         }
-
-        Ok(())
     }
 }
 
-fn get_deck_position(deck: &Deck) -> (u16, u16) {
-    let x = EMPTY_DECK_X;
-    let y = EMPTY_DECK_Y;
-
-    if deck.len() <= 1 {
-        (x, y)
-    } else {
-        (x - 1, y)
-    }
+fn get_time_text(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
 }
 
-fn get_pile_position(pile: &Pile) -> (u16, u16) {
-    let mut x = PILE_LAST_CARD_X;
-    let y = PILE_LAST_CARD_Y;
-
-    let all_cards_count = pile.len() as u16;
-    let visible_cards_count = pile.get_visible_cards().len() as u16;
-
-    x -= visible_cards_count.checked_sub(1).unwrap_or(0) * CARD_COLS.div(2);
-
-    if visible_cards_count < all_cards_count {
-        x -= 1;
-    }
-
-    (x, y)
-}
-
-fn get_suit_stack_position(i: u16) -> (u16, u16) {
-    let x = FIRST_SUIT_STACK_X + i * (CARD_COLS + GAP_BETWEEN_STACK_CARDS);
-    let y = FIRST_SUIT_STACK_Y;
-
-    (x, y)
-}
-
-fn get_stack_position(i: u16) -> (u16, u16) {
-    let x = FIRST_STACK_X + i * (CARD_COLS + GAP_BETWEEN_STACK_CARDS);
-    let y = FIRST_STACK_Y;
-
-    (x, y)
-}
-
-fn get_stack_last_card_position(stacks: &[Stack], i: u16) -> (u16, u16) {
-    let (mut x, mut y) = get_stack_position(i);
-
-    let cards_count = stacks[i as usize].len() as u16;
-    let offsets_count = cards_count.checked_sub(1).unwrap_or(0);
-
-    x += offsets_count * STACK_CARD_X_OFFSET;
-    y += offsets_count * STACK_CARD_Y_OFFSET;
-
-    (x, y)
-}
-
-fn get_index_of_card_in_stack_at(stacks: &[Stack], stack_i: u16, x: u16, y: u16) -> Option<u16> {
-    let (mut card_x, mut card_y) = get_stack_last_card_position(stacks, stack_i);
-
-    let cards = stacks[stack_i as usize].get_all();
-
-    for (i, card) in cards.iter().enumerate().rev() {
-        if card.hidden {
-            return None;
-        }
-
-        if is_point_inside_card(x, y, card_x, card_y) {
-            return Some(i as u16);
-        }
-
-        if i > 0 {
-            card_x -= STACK_CARD_X_OFFSET;
-            card_y -= STACK_CARD_Y_OFFSET;
-        }
-    }
-
-    None
-}
-
-fn is_point_outside_of_table(x: u16, y: u16) -> bool {
-    x < PADDING_X || y < PADDING_Y || x >= PADDING_X + TABLE_COLS || y >= PADDING_Y + TABLE_ROWS
-}
-
-fn is_point_inside_card(px: u16, py: u16, cx: u16, cy: u16) -> bool {
-    px >= cx && px < cx + CARD_COLS && py >= cy && py < cy + CARD_ROWS
-}
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,298 @@
+use std::{env, fs, path::PathBuf};
+
+use crate::game::{backend::HeadlessBackend, moves::Move, Deal, Game, GameObject, Rules, Theme};
+
+use super::GameRenderer;
+
+const SEED: u64 = 5;
+/// The last stack with as many face down cards as a deal gives it and a
+/// full suit face up on them.
+const LONGEST_STACK_BOARD: &str = "\
+foundations: - - - -
+pile:
+deck: A♣ 2♣ 3♣ 4♣ 5♣ 7♣ 8♣ 10♣ J♣ Q♣ K♣ A♦ 2♦ 3♦ 5♦ 6♦ 7♦ 9♦ 10♦ J♦ Q♦ K♦ A♠ 5♠ 7♠ 9♠ J♠ K♠ 2♥ 4♥ 6♥ 8♥ 10♥ Q♥
+1:
+2:
+3:
+4:
+5:
+6:
+7: #3♠ #4♦ #6♣ #8♦ #9♣ K♥ Q♠ J♥ 10♠ 9♥ 8♠ 7♥ 6♠ 5♥ 4♠ 3♥ 2♠ A♥
+";
+
+/// The clock is stopped, so that the status bar doesn't depend on how long
+/// the test takes.
+fn get_game() -> Game {
+    let mut game = Game::new(Rules::default(), Deal::Seeded(SEED));
+    game.stopwatch.stop();
+
+    game
+}
+
+fn get_renderer(columns: u16, rows: u16, theme: Theme) -> GameRenderer<HeadlessBackend> {
+    let backend = HeadlessBackend::new(columns, rows);
+    let mut renderer = GameRenderer::with_backend(backend, Rules::default().stacks_count, theme);
+    renderer.init().expect("the headless backend doesn't fail");
+
+    renderer
+}
+
+fn render(renderer: &mut GameRenderer<HeadlessBackend>, game: &Game) {
+    renderer.render(game);
+    renderer
+        .present()
+        .expect("the headless backend doesn't fail");
+}
+
+/// Snapshots are kept next to the tests. A missing or outdated one is
+/// written again when `UPDATE_SNAPSHOTS` is set, to be reviewed in the diff.
+fn assert_snapshot(name: &str, renderer: &GameRenderer<HeadlessBackend>) {
+    let snapshot = renderer.backend.get_snapshot();
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/game/renderer/snapshots")
+        .join(format!("{name}.txt"));
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, &snapshot).expect("the snapshot can be written");
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "No snapshot at {}, run the tests with UPDATE_SNAPSHOTS=1 to write it",
+            path.display()
+        )
+    });
+    assert!(
+        snapshot == expected,
+        "The screen doesn't match {}:\n{snapshot}",
+        path.display()
+    );
+}
+
+/// Takes a point on the table and gives the same point on the screen.
+fn get_screen_point(renderer: &GameRenderer<HeadlessBackend>, (x, y): (u16, u16)) -> (u16, u16) {
+    (x + renderer.layout.padding_x, y + renderer.layout.padding_y)
+}
+
+fn get_object_at(
+    renderer: &GameRenderer<HeadlessBackend>,
+    game: &Game,
+    point: (u16, u16),
+) -> GameObject {
+    let (column, row) = get_screen_point(renderer, point);
+    renderer.get_object_at(game, row, column)
+}
+
+#[test]
+fn renders_new_game_with_large_cards() {
+    let game = get_game();
+    let mut renderer = get_renderer(100, 44, Theme::Classic);
+
+    render(&mut renderer, &game);
+
+    assert_snapshot("new_game_large_cards", &renderer);
+}
+
+#[test]
+fn renders_new_game_with_small_cards() {
+    let game = get_game();
+    let mut renderer = get_renderer(60, 40, Theme::Classic);
+
+    render(&mut renderer, &game);
+
+    assert_snapshot("new_game_small_cards", &renderer);
+}
+
+#[test]
+fn renders_too_small_terminal() {
+    let game = get_game();
+    let mut renderer = get_renderer(40, 20, Theme::Classic);
+
+    render(&mut renderer, &game);
+
+    assert_snapshot("too_small", &renderer);
+}
+
+/// Every face up card of the longest stack keeps its value in sight, even
+/// on the smallest table.
+#[test]
+fn renders_longest_stack_on_smallest_table() {
+    let mut game =
+        Game::from_board(Rules::default(), LONGEST_STACK_BOARD).expect("the board is valid");
+    game.stopwatch.stop();
+    let mut renderer = get_renderer(48, 39, Theme::Classic);
+
+    render(&mut renderer, &game);
+
+    assert_snapshot("longest_stack_smallest_table", &renderer);
+}
+
+#[test]
+fn renders_suits_in_four_colors() {
+    let mut game = get_game();
+    game.make_move(Move::Draw);
+    let mut renderer = get_renderer(100, 44, Theme::FourColor);
+
+    render(&mut renderer, &game);
+
+    assert_snapshot("four_color", &renderer);
+}
+
+#[test]
+fn renders_dragged_card_and_stalemate() {
+    let game = get_game();
+    let mut renderer = get_renderer(100, 44, Theme::Classic);
+
+    renderer.select_object(&game, GameObject::LastCardOfStack(3));
+    renderer.set_selected_object_position(20, 30);
+    renderer.render(&game);
+    renderer.render_stalemate();
+    renderer
+        .present()
+        .expect("the headless backend doesn't fail");
+
+    assert_snapshot("dragged_card_and_stalemate", &renderer);
+}
+
+/// Only the changed cells are sent after the first frame, what's left on
+/// the screen has to match a frame drawn from scratch.
+#[test]
+fn renders_only_changes_like_whole_frame() {
+    let mut game = get_game();
+    let mut renderer = get_renderer(100, 44, Theme::Classic);
+    render(&mut renderer, &game);
+
+    renderer.render_stats(&game, &Default::default());
+    renderer
+        .present()
+        .expect("the headless backend doesn't fail");
+    game.make_move(Move::Draw);
+    game.make_move(Move::Draw);
+    render(&mut renderer, &game);
+
+    let mut whole_frame_renderer = get_renderer(100, 44, Theme::Classic);
+    render(&mut whole_frame_renderer, &game);
+
+    assert_eq!(
+        renderer.backend.get_snapshot(),
+        whole_frame_renderer.backend.get_snapshot()
+    );
+}
+
+#[test]
+fn redraws_whole_frame_after_resize() {
+    let game = get_game();
+    let mut renderer = get_renderer(100, 44, Theme::Classic);
+    render(&mut renderer, &game);
+
+    renderer.backend = HeadlessBackend::new(60, 40);
+    renderer.resize(60, 40);
+    render(&mut renderer, &game);
+
+    assert_snapshot("new_game_small_cards", &renderer);
+}
+
+#[test]
+fn finds_objects_at_points_on_screen() {
+    let mut game = get_game();
+    game.make_move(Move::Draw);
+    let mut renderer = get_renderer(100, 44, Theme::Classic);
+    render(&mut renderer, &game);
+    let layout = renderer.layout;
+
+    let deck = layout.get_deck_position(&game.deck);
+    assert_eq!(get_object_at(&renderer, &game, deck), GameObject::Deck);
+
+    let pile = layout.get_pile_last_card_position();
+    assert_eq!(get_object_at(&renderer, &game, pile), GameObject::Pile);
+
+    for i in 0..4 {
+        let suit_stack = layout.get_suit_stack_position(i);
+        assert_eq!(
+            get_object_at(&renderer, &game, suit_stack),
+            GameObject::SuitStack(i)
+        );
+    }
+
+    for (i, stack) in game.stacks.iter().enumerate() {
+        let i = i as u16;
+        let (x, y) = layout.get_stack_last_card_position(i, stack);
+        let bottom_right = (x + layout.card_cols - 1, y + layout.card_rows - 1);
+        assert_eq!(
+            get_object_at(&renderer, &game, bottom_right),
+            GameObject::LastCardOfStack(i)
+        );
+    }
+
+    let hidden_card = layout.get_stack_card_position(6, &game.stacks[6], 0);
+    assert_eq!(
+        get_object_at(&renderer, &game, hidden_card),
+        GameObject::None
+    );
+
+    let (x, y) = layout.get_stack_position(0);
+    let below_stack = (x, y + layout.card_rows);
+    assert_eq!(
+        get_object_at(&renderer, &game, below_stack),
+        GameObject::None
+    );
+
+    assert_eq!(renderer.get_object_at(&game, 0, 0), GameObject::None);
+}
+
+#[test]
+fn finds_card_under_the_symbol_drawn_for_it() {
+    let game = get_game();
+    let mut renderer = get_renderer(100, 44, Theme::Classic);
+    render(&mut renderer, &game);
+
+    let stack = &game.stacks[2];
+    let (x, y) = renderer.layout.get_stack_last_card_position(2, stack);
+    let (column, row) = get_screen_point(&renderer, (x + 1, y + 1));
+
+    let card = stack.last().expect("the stack is dealt");
+    let value = card.value.to_string();
+    let symbol = renderer.backend.get_cell(column, row).symbol;
+    assert_eq!(Some(symbol), value.chars().next());
+    assert_eq!(
+        renderer.get_object_at(&game, row, column),
+        GameObject::LastCardOfStack(2)
+    );
+}
+
+#[test]
+fn finds_nothing_while_terminal_is_too_small() {
+    let game = get_game();
+    let mut renderer = get_renderer(40, 20, Theme::Classic);
+    render(&mut renderer, &game);
+
+    for (column, row) in [(0, 0), (10, 5), (20, 10), (39, 19)] {
+        assert_eq!(renderer.get_object_at(&game, row, column), GameObject::None);
+    }
+}
+
+/// The renderer can be left holding an object that the game no longer has,
+/// it draws what's left instead of failing.
+#[test]
+fn renders_selection_left_over_from_previous_deal() {
+    let mut game = get_game();
+    game.make_move(Move::Draw);
+    let mut renderer = get_renderer(100, 44, Theme::Classic);
+
+    for object in [
+        GameObject::Pile,
+        GameObject::LastCardOfStack(6),
+        GameObject::CardOfStack {
+            stack_i: 6,
+            card_i: 5,
+        },
+    ] {
+        renderer.select_object(&game, object);
+        assert_eq!(renderer.get_selected_object(), object);
+
+        let mut dealt_game = get_game();
+        dealt_game.stacks[6].clear();
+        render(&mut renderer, &dealt_game);
+    }
+}
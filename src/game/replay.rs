@@ -0,0 +1,167 @@
+use std::io::Result;
+
+use super::{game_record::GameRecord, renderer::GameRenderer, Game, Theme};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+enum ReplayEvent {
+    StepForward,
+    StepBack,
+    JumpToStart,
+    JumpToEnd,
+    Resize { columns: u16, rows: u16 },
+    Exit,
+    None,
+}
+
+fn wait_for_event() -> Result<ReplayEvent> {
+    let event = match event::read()? {
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            ..
+        }) => ReplayEvent::Exit,
+        Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) => match code {
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') | KeyCode::Enter => {
+                ReplayEvent::StepForward
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Backspace => ReplayEvent::StepBack,
+            KeyCode::Home | KeyCode::Char('g') => ReplayEvent::JumpToStart,
+            KeyCode::End | KeyCode::Char('G') => ReplayEvent::JumpToEnd,
+            KeyCode::Esc | KeyCode::Char('q') => ReplayEvent::Exit,
+            _ => ReplayEvent::None,
+        },
+        Event::Resize(columns, rows) => ReplayEvent::Resize { columns, rows },
+        _ => ReplayEvent::None,
+    };
+
+    Ok(event)
+}
+
+/// Steps through a recorded game. Moves are made and taken back with the
+/// game's own move and undo, so the score and the counters follow along.
+struct ReplayEngine {
+    renderer: GameRenderer,
+    moves_made: usize,
+    record: GameRecord,
+    game: Game,
+    errors: Vec<String>,
+}
+
+impl ReplayEngine {
+    fn new(record: GameRecord, theme: Theme) -> Self {
+        let game = record.start();
+
+        Self {
+            renderer: GameRenderer::new(game.stacks.len(), theme),
+            moves_made: 0,
+            record,
+            game,
+            errors: Vec::new(),
+        }
+    }
+
+    fn start(&mut self) -> Result<()> {
+        self.renderer.init()?;
+
+        self.render();
+
+        loop {
+            let event = wait_for_event().unwrap_or_else(|error| {
+                self.report(format!("Something goes wrong: {error}"));
+                ReplayEvent::Exit
+            });
+
+            let should_rerender = match event {
+                ReplayEvent::StepForward => self.step_forward(),
+                ReplayEvent::StepBack => self.step_back(),
+                ReplayEvent::JumpToStart => {
+                    while self.step_back() {}
+                    true
+                }
+                ReplayEvent::JumpToEnd => {
+                    while self.step_forward() {}
+                    true
+                }
+                ReplayEvent::Resize { columns, rows } => {
+                    self.renderer.resize(columns, rows);
+                    true
+                }
+                ReplayEvent::Exit => break,
+                ReplayEvent::None => false,
+            };
+
+            if should_rerender {
+                self.render();
+            }
+        }
+
+        self.renderer.cleanup();
+        for error in self.errors.drain(..) {
+            eprintln!("{error}");
+        }
+
+        Ok(())
+    }
+
+    fn step_forward(&mut self) -> bool {
+        let Some(&mv) = self.record.moves().get(self.moves_made) else {
+            return false;
+        };
+
+        self.moves_made += 1;
+        self.game.make_move(mv)
+    }
+
+    fn step_back(&mut self) -> bool {
+        if self.moves_made == 0 {
+            return false;
+        }
+
+        self.moves_made -= 1;
+        self.game.undo()
+    }
+
+    fn render(&mut self) {
+        let moves = self.record.moves();
+        let next_move = moves.get(self.moves_made).copied();
+
+        self.renderer
+            .set_hint(next_move.map(|mv| self.game.get_move_objects(mv)));
+
+        let position = match next_move {
+            Some(mv) => format!("Move {} of {}: {mv}", self.moves_made + 1, moves.len()),
+            None => format!("End of the game, {} moves", moves.len()),
+        };
+        let text = format!("{position}   [←/→] Step   [Home/End] Jump   [Esc] Quit");
+
+        self.renderer.render(&self.game);
+        self.renderer.render_replay_bar(&text);
+
+        if let Err(error) = self.renderer.present() {
+            self.report(format!("Error rendering game: {error}"));
+        }
+    }
+
+    /// Errors can't be printed while the replay takes over the terminal,
+    /// they are kept until it's given back, each only once.
+    fn report(&mut self, error: String) {
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+}
+
+impl GameRecord {
+    pub fn replay(&self, theme: Theme) {
+        let result = ReplayEngine::new(self.clone(), theme).start();
+
+        if let Err(error) = result {
+            println!("Error during the replay: {error}");
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use super::SplitMix64;
+
+/// The first numbers of the reference implementation for seed 0. Seeds are
+/// shared between players, a change here would deal them other games.
+#[test]
+fn generates_reference_numbers() {
+    let mut rng = SplitMix64::new(0);
+
+    let numbers = [rng.next_u64(), rng.next_u64(), rng.next_u64()];
+
+    assert_eq!(
+        numbers,
+        [
+            0xE220_A839_7B1D_CDAF,
+            0x6E78_9E6A_A1B9_65F4,
+            0x06C4_5D18_8009_454F
+        ]
+    );
+}
+
+#[test]
+fn generates_same_numbers_for_same_seed() {
+    let mut rng = SplitMix64::new(5);
+    let mut same_rng = SplitMix64::new(5);
+
+    for _ in 0..100 {
+        assert_eq!(rng.next_u64(), same_rng.next_u64());
+    }
+}
+
+#[test]
+fn keeps_numbers_below_bound() {
+    let mut rng = SplitMix64::new(5);
+
+    for bound in 1..100 {
+        assert!(rng.next_below(bound) < bound);
+    }
+}
+
+#[test]
+fn shuffles_into_same_items_in_other_order() {
+    let items: Vec<u32> = (0..52).collect();
+    let mut shuffled = items.clone();
+
+    SplitMix64::new(5).shuffle(&mut shuffled);
+
+    assert_ne!(shuffled, items);
+    shuffled.sort_unstable();
+    assert_eq!(shuffled, items);
+}
@@ -0,0 +1,22 @@
+use super::scoring::ScoringMode;
+
+pub const MAX_STACKS_COUNT: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rules {
+    pub stacks_count: usize,
+    pub draw_count: usize,
+    pub passes_limit: Option<usize>,
+    pub scoring_mode: ScoringMode,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            stacks_count: 7,
+            draw_count: 3,
+            passes_limit: None,
+            scoring_mode: ScoringMode::Standard,
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::{
+    io::{self, Error, ErrorKind},
+    str::{FromStr, Lines},
+    time::Duration,
+};
+
+use crate::storage;
+
+use super::{
+    board::parse_cards,
+    card::Card,
+    card_collections::{Deck, Pile, Stack},
+    history::{History, Record},
+    moves::Move,
+    stats::Stats,
+    stopwatch::Stopwatch,
+    Deal, Game, Rules, ScoringMode, MAX_STACKS_COUNT,
+};
+
+const SAVE_FILE: &str = "save";
+const SAVE_HEADER: &str = "consolitaire save";
+const SAVE_VERSION: u32 = 1;
+
+/// The game left unfinished is kept in a text file: a header with the format
+/// version, then one `name values...` line per part of the game. Cards are
+/// written as value and suit (`10♥`), `#` in front marks a face down card.
+/// Undo records are written as `move,revealed,drawn,score`.
+impl Game {
+    pub fn save(&self) -> io::Result<()> {
+        storage::write(SAVE_FILE, &self.to_save_text())
+    }
+
+    pub fn load_saved() -> io::Result<Option<Game>> {
+        let Some(text) = storage::read(SAVE_FILE)? else {
+            return Ok(None);
+        };
+
+        Game::from_save_text(&text)
+            .map(Some)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error))
+    }
+
+    /// Keeps the game for the next launch unless it's over or hasn't been
+    /// started. The bankroll is settled first, so that the resumed game only
+    /// settles what changes after.
+    pub fn save_unfinished(&mut self) -> io::Result<()> {
+        if self.is_won() || self.moves_count == 0 {
+            return Game::delete_save();
+        }
+
+        self.settle_bankroll()?;
+        self.save()
+    }
+
+    /// Drops the saved game without playing it. It counts as lost, unless
+    /// it was counted when it was left.
+    pub fn give_up_saved(&self) -> io::Result<()> {
+        if !self.is_counted_in_stats {
+            Stats::load()?.record_loss(self)?;
+        }
+
+        Game::delete_save()
+    }
+
+    pub fn delete_save() -> io::Result<()> {
+        storage::remove(SAVE_FILE)
+    }
+
+    fn to_save_text(&self) -> String {
+        let mut lines = vec![
+            format!("{SAVE_HEADER} {SAVE_VERSION}"),
+            get_rules_line(&self.rules),
+            get_deal_line(self.deal),
+            format!("passes {}", self.passes),
+            format!("moves {}", self.moves_count),
+            format!("score {}", self.scoring.score()),
+            format!("settled {}", self.settled_score),
+            format!("counted {}", self.is_counted_in_stats as u8),
+            format!("elapsed {}", self.stopwatch.elapsed().as_millis()),
+            get_cards_line("deck", self.deck.get_cards()),
+            get_cards_line(&format!("pile {}", self.pile.size()), self.pile.get_cards()),
+        ];
+
+        for stack in &self.suit_stacks {
+            lines.push(get_cards_line("foundation", stack));
+        }
+        for stack in &self.stacks {
+            lines.push(get_cards_line("stack", stack));
+        }
+
+        let done = self.history.get_done().iter().map(|record| {
+            format!(
+                "{},{},{},{}",
+                record.mv,
+                record.revealed as u8,
+                record.drawn,
+                record.scoring.score()
+            )
+        });
+        lines.push(get_items_line("done", done));
+
+        let undone = self.history.get_undone().iter().map(Move::to_string);
+        lines.push(get_items_line("undone", undone));
+
+        lines.join("\n") + "\n"
+    }
+
+    fn from_save_text(text: &str) -> Result<Game, String> {
+        let mut lines = text.lines();
+
+        let version: u32 = parse_value("version", next_line(&mut lines, SAVE_HEADER)?)?;
+        if version != SAVE_VERSION {
+            return Err(format!("Unsupported save version {version}"));
+        }
+
+        let rules = parse_rules(next_line(&mut lines, "rules")?)?;
+        let deal = parse_deal(next_line(&mut lines, "deal")?)?;
+        let mut game = Game::empty(rules, deal);
+
+        game.passes = parse_value("passes", next_line(&mut lines, "passes")?)?;
+        if game.passes == 0 {
+            return Err("Invalid passes '0'".to_string());
+        }
+        game.moves_count = parse_value("moves", next_line(&mut lines, "moves")?)?;
+        let score = parse_value("score", next_line(&mut lines, "score")?)?;
+        game.scoring = game.scoring.with_score(score);
+        game.settled_score = parse_value("settled", next_line(&mut lines, "settled")?)?;
+        game.is_counted_in_stats = match next_line(&mut lines, "counted")? {
+            "0" => false,
+            "1" => true,
+            counted => return Err(format!("Invalid counted '{counted}'")),
+        };
+        let elapsed = parse_value("elapsed", next_line(&mut lines, "elapsed")?)?;
+        game.stopwatch = Stopwatch::resumed(Duration::from_millis(elapsed));
+
+        game.deck = Deck::from_cards(parse_cards(next_line(&mut lines, "deck")?)?);
+
+        let pile = next_line(&mut lines, "pile")?;
+        let (size, cards) = pile.split_once(' ').unwrap_or((pile, ""));
+        game.pile = Pile::with_cards(parse_value("pile", size)?, parse_cards(cards)?);
+
+        for stack in &mut game.suit_stacks {
+            *stack = parse_cards(next_line(&mut lines, "foundation")?)?;
+        }
+        for stack in &mut game.stacks {
+            *stack = parse_cards(next_line(&mut lines, "stack")?)?;
+        }
+
+        let done = next_line(&mut lines, "done")?
+            .split_whitespace()
+            .map(|record| parse_record(&game, record))
+            .collect::<Result<_, _>>()?;
+        let undone = next_line(&mut lines, "undone")?
+            .split_whitespace()
+            .map(Move::from_str)
+            .collect::<Result<_, _>>()?;
+        game.history = History::new(done, undone);
+
+        game.check_cards()?;
+        game.check_history()?;
+
+        Ok(game)
+    }
+
+    /// The undo records are taken back on a copy of the game, which has to
+    /// end up where the game started, then the moves are made again, which
+    /// has to end up at the saved position. The moves to redo have to be
+    /// possible from there.
+    fn check_history(&self) -> Result<(), String> {
+        let invalid_history = || "The undo history doesn't match the cards".to_string();
+
+        let mut game = self.clone();
+        for record in self.history.get_done().iter().rev() {
+            if !game.can_revert_move(record) {
+                return Err(invalid_history());
+            }
+            game.revert_move(*record);
+        }
+
+        let is_dealt_as_saved = match self.deal {
+            Some(deal) => game.has_same_cards(&Game::new(self.rules, deal)),
+            None => true,
+        };
+        if game.passes != 1 || !is_dealt_as_saved {
+            return Err(invalid_history());
+        }
+
+        for record in self.history.get_done() {
+            let is_same_move = game
+                .apply_move(record.mv)
+                .is_some_and(|made| made.revealed == record.revealed && made.drawn == record.drawn);
+            if !is_same_move {
+                return Err(invalid_history());
+            }
+        }
+
+        if !game.has_same_cards(self) || game.passes != self.passes {
+            return Err(invalid_history());
+        }
+
+        for &mv in self.history.get_undone().iter().rev() {
+            if game.apply_move(mv).is_none() {
+                return Err(invalid_history());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn has_same_cards(&self, other: &Game) -> bool {
+        self.deck == other.deck
+            && self.pile == other.pile
+            && self.suit_stacks == other.suit_stacks
+            && self.stacks == other.stacks
+    }
+}
+
+pub(super) fn get_rules_line(rules: &Rules) -> String {
+    let passes_limit = match rules.passes_limit {
+        Some(limit) => limit.to_string(),
+        None => "unlimited".to_string(),
+    };
+    let scoring_mode = match rules.scoring_mode {
+        ScoringMode::Standard => "standard",
+        ScoringMode::Vegas => "vegas",
+    };
+
+    format!(
+        "rules {} {} {passes_limit} {scoring_mode}",
+        rules.stacks_count, rules.draw_count
+    )
+}
+
+pub(super) fn get_deal_line(deal: Option<Deal>) -> String {
+    match deal {
+        Some(Deal::Seeded(seed)) => format!("deal seed {seed}"),
+        Some(Deal::Microsoft(number)) => format!("deal microsoft {number}"),
+        None => "deal board".to_string(),
+    }
+}
+
+fn get_cards_line(name: &str, stack: &Stack) -> String {
+    get_items_line(name, stack.get_all().iter().map(Card::to_string))
+}
+
+fn get_items_line(name: &str, items: impl Iterator<Item = String>) -> String {
+    items.fold(name.to_string(), |line, item| line + " " + &item)
+}
+
+/// Takes the next line, which must start with `name`, and returns the rest
+/// of it.
+pub(super) fn next_line<'a>(lines: &mut Lines<'a>, name: &str) -> Result<&'a str, String> {
+    let line = lines
+        .next()
+        .ok_or_else(|| format!("Expected '{name}', found the end of the file"))?;
+
+    match line.strip_prefix(name) {
+        Some("") => Ok(""),
+        Some(rest) if rest.starts_with(' ') => Ok(&rest[1..]),
+        _ => Err(format!("Expected '{name}', found '{line}'")),
+    }
+}
+
+pub(super) fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid {name} '{value}'"))
+}
+
+pub(super) fn parse_rules(text: &str) -> Result<Rules, String> {
+    let invalid_rules = || format!("Invalid rules '{text}'");
+
+    let [stacks_count, draw_count, passes_limit, scoring_mode] = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| invalid_rules())?;
+
+    let stacks_count = parse_value("rules", stacks_count)?;
+    let draw_count = parse_value("rules", draw_count)?;
+    if !(1..=MAX_STACKS_COUNT).contains(&stacks_count) || draw_count == 0 {
+        return Err(invalid_rules());
+    }
+
+    Ok(Rules {
+        stacks_count,
+        draw_count,
+        passes_limit: match passes_limit {
+            "unlimited" => None,
+            limit => Some(parse_value("rules", limit)?),
+        },
+        scoring_mode: match scoring_mode {
+            "standard" => ScoringMode::Standard,
+            "vegas" => ScoringMode::Vegas,
+            _ => return Err(invalid_rules()),
+        },
+    })
+}
+
+pub(super) fn parse_deal(text: &str) -> Result<Option<Deal>, String> {
+    match text.split_once(' ') {
+        Some(("seed", seed)) => Ok(Some(Deal::Seeded(parse_value("deal", seed)?))),
+        Some(("microsoft", number)) => Ok(Some(Deal::Microsoft(parse_value("deal", number)?))),
+        None if text == "board" => Ok(None),
+        _ => Err(format!("Invalid deal '{text}'")),
+    }
+}
+
+fn parse_record(game: &Game, text: &str) -> Result<Record, String> {
+    let invalid_record = || format!("Invalid undo record '{text}'");
+
+    let [mv, revealed, drawn, score] = text
+        .split(',')
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| invalid_record())?;
+
+    Ok(Record {
+        mv: mv.parse()?,
+        revealed: match revealed {
+            "0" => false,
+            "1" => true,
+            _ => return Err(invalid_record()),
+        },
+        drawn: parse_value("undo record", drawn)?,
+        scoring: game.scoring.with_score(parse_value("undo record", score)?),
+    })
+}
+
+#[cfg(test)]
+mod tests;
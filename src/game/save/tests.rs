@@ -0,0 +1,111 @@
+use crate::game::{moves::Move, Bankroll, Deal, Game, Rules, ScoringMode};
+
+/// The start of a winning line for seed 5, going through the deck twice.
+const MOVES: &str = "d w-t7 t6-t7 t6-t5 d d w-t7 w-t2 w-t4 d w-t5 d w-t2 w-f1 w-t1 d w-t3 \
+    w-t7 d d d w-t3 w-t5 t3:3-t5 t3-f2 d w-f3 t6-f3 t7:5-t6 t7-t4 t7-t4 w-t1 t2-t5";
+
+fn get_played_game() -> Game {
+    let mut game = Game::new(Rules::default(), Deal::Seeded(5));
+
+    for mv in MOVES.split_whitespace() {
+        let mv: Move = mv.parse().expect("the moves are written correctly");
+        assert!(game.make_move(mv), "{mv} can be made");
+    }
+    game.undo();
+    game.undo();
+
+    game
+}
+
+fn replace_line(text: &str, name: &str, line: &str) -> String {
+    text.lines()
+        .map(|l| {
+            if l.split(' ').next() == Some(name) {
+                line
+            } else {
+                l
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn loads_saved_game_as_it_was() {
+    let game = get_played_game();
+    let text = game.to_save_text();
+
+    let mut loaded = Game::from_save_text(&text).expect("the save is valid");
+    assert_eq!(loaded.to_save_text(), text);
+
+    assert!(loaded.redo());
+    while loaded.undo() {}
+    assert!(loaded.has_same_cards(&Game::new(Rules::default(), Deal::Seeded(5))));
+}
+
+#[test]
+fn rejects_records_out_of_range() {
+    let text = get_played_game().to_save_text();
+
+    for done in ["done t9-t1,0,0,0", "done t1:20-t2,0,0,0", "done w-f5,0,0,0"] {
+        let text = replace_line(&text, "done", done);
+        assert!(Game::from_save_text(&text).is_err(), "{done} is rejected");
+    }
+}
+
+#[test]
+fn rejects_records_not_leading_to_saved_cards() {
+    let text = get_played_game().to_save_text();
+
+    let text = replace_line(&text, "done", "done d,0,3,0");
+    assert!(Game::from_save_text(&text).is_err());
+}
+
+#[test]
+fn rejects_moves_to_redo_that_cant_be_made() {
+    let text = get_played_game().to_save_text();
+
+    let text = replace_line(&text, "undone", "undone t1-f1 t1-f1");
+    assert!(Game::from_save_text(&text).is_err());
+}
+
+#[test]
+fn rejects_zero_passes() {
+    let text = get_played_game().to_save_text();
+
+    let text = replace_line(&text, "passes", "passes 0");
+    assert!(Game::from_save_text(&text).is_err());
+}
+
+/// The bankroll took what the game had won when it was left, only what's
+/// won after it's resumed is added to it.
+#[test]
+fn settles_resumed_vegas_game_once() {
+    let rules = Rules {
+        scoring_mode: ScoringMode::Vegas,
+        ..Rules::default()
+    };
+    let mut moves = MOVES
+        .split_whitespace()
+        .map(|mv| mv.parse::<Move>().expect("the moves are written correctly"));
+    let mut game = Game::new(rules, Deal::Seeded(5));
+    for mv in moves.by_ref().take(14) {
+        assert!(game.make_move(mv), "{mv} can be made");
+    }
+    assert_eq!(game.score(), -47);
+
+    // What leaving the game settles, without saving the player's bankroll.
+    game.settled_score = game.score();
+    let mut loaded = Game::from_save_text(&game.to_save_text())
+        .expect("the save is valid")
+        .with_bankroll(Bankroll::with_balance(-47));
+
+    assert_eq!(loaded.settled_score, -47);
+    assert_eq!(loaded.bankroll_text().as_deref(), Some("-$47"));
+
+    for mv in moves.take(11) {
+        assert!(loaded.make_move(mv), "{mv} can be made");
+    }
+    assert_eq!(loaded.score(), -42);
+    assert_eq!(loaded.bankroll_text().as_deref(), Some("-$42"));
+}
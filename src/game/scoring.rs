@@ -0,0 +1,141 @@
+use std::{fmt, time::Duration};
+
+use super::{history::Record, moves::Move};
+
+const PILE_TO_STACK_POINTS: i32 = 5;
+const TO_SUIT_STACK_POINTS: i32 = 10;
+const REVEAL_CARD_POINTS: i32 = 5;
+const SUIT_STACK_TO_STACK_POINTS: i32 = -15;
+const RECYCLE_DRAW_ONE_POINTS: i32 = -100;
+const RECYCLE_DRAW_MANY_POINTS: i32 = -20;
+const TIME_BONUS_DIVIDEND: u64 = 700_000;
+const TIME_BONUS_MIN_SECONDS: u64 = 30;
+
+const VEGAS_GAME_COST: i32 = -52;
+const VEGAS_TO_SUIT_STACK_DOLLARS: i32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScoringMode {
+    /// Standard Windows Klondike scoring. The score never drops below zero.
+    Standard,
+    /// The game costs $52 and every card sent to a suit stack pays $5.
+    Vegas,
+}
+
+impl ScoringMode {
+    /// How many times the player may go through the deck unless the rules
+    /// say otherwise, `None` when recycling is unlimited.
+    pub fn default_passes_limit(&self, draw_count: usize) -> Option<usize> {
+        match (self, draw_count) {
+            (Self::Standard, _) => None,
+            (Self::Vegas, 1) => Some(1),
+            (Self::Vegas, _) => Some(3),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Scoring {
+    mode: ScoringMode,
+    score: i32,
+    draw_count: usize,
+}
+
+impl Scoring {
+    pub fn new(mode: ScoringMode, draw_count: usize) -> Self {
+        let mut scoring = Self {
+            mode,
+            score: 0,
+            draw_count,
+        };
+        scoring.reset();
+        scoring
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    pub fn with_score(mut self, score: i32) -> Self {
+        self.score = score;
+        self
+    }
+
+    pub fn reset(&mut self) {
+        self.score = match self.mode {
+            ScoringMode::Standard => 0,
+            ScoringMode::Vegas => VEGAS_GAME_COST,
+        };
+    }
+
+    pub fn on_move(&mut self, record: &Record) {
+        match self.mode {
+            ScoringMode::Standard => self.on_standard_move(record),
+            ScoringMode::Vegas => self.on_vegas_move(record),
+        }
+    }
+
+    pub fn on_win(&mut self, elapsed: Duration) {
+        let seconds = elapsed.as_secs();
+
+        if self.mode == ScoringMode::Standard && seconds >= TIME_BONUS_MIN_SECONDS {
+            self.add((TIME_BONUS_DIVIDEND / seconds) as i32);
+        }
+    }
+
+    fn on_standard_move(&mut self, record: &Record) {
+        let mut points = match record.mv {
+            Move::Draw if record.drawn == 0 => self.recycle_points(),
+            Move::Draw | Move::StackToStack { .. } => 0,
+            Move::PileToStack(_) => PILE_TO_STACK_POINTS,
+            Move::PileToSuitStack(_) | Move::StackToSuitStack { .. } => TO_SUIT_STACK_POINTS,
+            Move::SuitStackToStack { .. } => SUIT_STACK_TO_STACK_POINTS,
+        };
+
+        if record.revealed {
+            points += REVEAL_CARD_POINTS;
+        }
+
+        self.add(points);
+    }
+
+    fn on_vegas_move(&mut self, record: &Record) {
+        self.score += match record.mv {
+            Move::PileToSuitStack(_) | Move::StackToSuitStack { .. } => VEGAS_TO_SUIT_STACK_DOLLARS,
+            Move::SuitStackToStack { .. } => -VEGAS_TO_SUIT_STACK_DOLLARS,
+            _ => 0,
+        };
+    }
+
+    fn recycle_points(&self) -> i32 {
+        if self.draw_count == 1 {
+            RECYCLE_DRAW_ONE_POINTS
+        } else {
+            RECYCLE_DRAW_MANY_POINTS
+        }
+    }
+
+    fn add(&mut self, points: i32) {
+        self.score = (self.score + points).max(0);
+    }
+}
+
+pub fn format_dollars(amount: i64) -> String {
+    if amount < 0 {
+        format!("-${}", -amount)
+    } else {
+        format!("${amount}")
+    }
+}
+
+impl fmt::Display for Scoring {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            ScoringMode::Standard => write!(formatter, "{}", self.score),
+            ScoringMode::Vegas => write!(formatter, "{}", format_dollars(self.score as i64)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
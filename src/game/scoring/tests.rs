@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use crate::game::{history::Record, moves::Move, Game, Rules};
+
+use super::{Scoring, ScoringMode};
+
+/// A king to send up, that turns the queen under it face up.
+const REVEALING_BOARD: &str = "\
+foundations: Q♣ K♦ K♠ J♥
+pile:
+deck:
+1: #Q♥ K♣
+2: K♥
+";
+
+fn get_record(mv: Move, revealed: bool, drawn: usize) -> Record {
+    Record {
+        mv,
+        revealed,
+        drawn,
+        scoring: Scoring::new(ScoringMode::Standard, 3),
+    }
+}
+
+/// The score after a move made with the given score before it.
+fn score_move(score: i32, draw_count: usize, record: Record) -> i32 {
+    let mut scoring = Scoring::new(ScoringMode::Standard, draw_count).with_score(score);
+    scoring.on_move(&record);
+
+    scoring.score()
+}
+
+#[test]
+fn scores_cards_sent_to_suit_stacks() {
+    let from_pile = get_record(Move::PileToSuitStack(0), false, 0);
+    let from_stack = get_record(Move::StackToSuitStack { from: 0, into: 0 }, false, 0);
+
+    assert_eq!(score_move(0, 3, from_pile), 10);
+    assert_eq!(score_move(0, 3, from_stack), 10);
+}
+
+#[test]
+fn scores_cards_from_pile_to_stacks() {
+    assert_eq!(
+        score_move(0, 3, get_record(Move::PileToStack(0), false, 0)),
+        5
+    );
+}
+
+#[test]
+fn scores_revealed_cards() {
+    let stack_to_stack = Move::StackToStack {
+        from: 0,
+        into: 1,
+        count: 2,
+    };
+    let stack_to_suit_stack = Move::StackToSuitStack { from: 0, into: 0 };
+
+    assert_eq!(score_move(0, 3, get_record(stack_to_stack, false, 0)), 0);
+    assert_eq!(score_move(0, 3, get_record(stack_to_stack, true, 0)), 5);
+    assert_eq!(
+        score_move(0, 3, get_record(stack_to_suit_stack, true, 0)),
+        15
+    );
+}
+
+#[test]
+fn takes_points_for_cards_back_from_suit_stacks() {
+    let mv = Move::SuitStackToStack { from: 0, into: 0 };
+
+    assert_eq!(score_move(20, 3, get_record(mv, false, 0)), 5);
+}
+
+/// A draw of no cards is the pile turned back into the deck.
+#[test]
+fn takes_points_for_recycling() {
+    assert_eq!(score_move(150, 1, get_record(Move::Draw, false, 0)), 50);
+    assert_eq!(score_move(150, 3, get_record(Move::Draw, false, 0)), 130);
+    assert_eq!(score_move(150, 3, get_record(Move::Draw, false, 3)), 150);
+}
+
+#[test]
+fn keeps_score_from_dropping_below_zero() {
+    let mv = Move::SuitStackToStack { from: 0, into: 0 };
+
+    assert_eq!(score_move(10, 3, get_record(mv, false, 0)), 0);
+    assert_eq!(score_move(10, 1, get_record(Move::Draw, false, 0)), 0);
+}
+
+#[test]
+fn adds_time_bonus_on_win() {
+    let mut scoring = Scoring::new(ScoringMode::Standard, 3).with_score(100);
+    scoring.on_win(Duration::from_secs(100));
+    assert_eq!(scoring.score(), 7100);
+
+    let mut quick_scoring = Scoring::new(ScoringMode::Standard, 3).with_score(100);
+    quick_scoring.on_win(Duration::from_secs(29));
+    assert_eq!(quick_scoring.score(), 100);
+}
+
+#[test]
+fn charges_vegas_game_and_pays_for_cards_on_suit_stacks() {
+    let mut scoring = Scoring::new(ScoringMode::Vegas, 3);
+    assert_eq!(scoring.score(), -52);
+
+    scoring.on_move(&get_record(Move::PileToSuitStack(0), false, 0));
+    scoring.on_move(&get_record(
+        Move::StackToSuitStack { from: 0, into: 0 },
+        true,
+        0,
+    ));
+    assert_eq!(scoring.score(), -42);
+
+    scoring.on_move(&get_record(
+        Move::SuitStackToStack { from: 0, into: 0 },
+        false,
+        0,
+    ));
+    scoring.on_move(&get_record(Move::PileToStack(0), false, 0));
+    scoring.on_move(&get_record(Move::Draw, false, 0));
+    assert_eq!(scoring.score(), -47);
+
+    scoring.on_win(Duration::from_secs(100));
+    assert_eq!(scoring.score(), -47);
+    assert_eq!(scoring.to_string(), "-$47");
+}
+
+#[test]
+fn gives_points_back_on_undo() {
+    let mut game = Game::from_board(Rules::default(), REVEALING_BOARD).expect("the board is valid");
+
+    assert!(game.make_move(Move::StackToSuitStack { from: 0, into: 0 }));
+    assert_eq!(game.score(), 15);
+
+    assert!(game.undo());
+    assert_eq!(game.score(), 0);
+}
@@ -0,0 +1,115 @@
+use std::io::Result;
+
+use crossterm::style::Color;
+
+use super::backend::Backend;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Color,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            symbol: ' ',
+            color: Color::Reset,
+        }
+    }
+}
+
+/// The screen kept off the terminal. A frame is drawn into the cells, then
+/// only the cells that differ from the frame shown before are sent to the
+/// backend.
+pub struct Screen {
+    columns: u16,
+    rows: u16,
+    cells: Vec<Cell>,
+    shown_cells: Option<Vec<Cell>>,
+    color: Color,
+}
+
+impl Screen {
+    pub fn new(columns: u16, rows: u16) -> Self {
+        Self {
+            columns,
+            rows,
+            cells: vec![Cell::default(); columns as usize * rows as usize],
+            shown_cells: None,
+            color: Color::Reset,
+        }
+    }
+
+    pub fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    pub fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// What the terminal shows is unknown after it's resized, so the next
+    /// frame is sent whole.
+    pub fn resize(&mut self, columns: u16, rows: u16) {
+        *self = Self::new(columns, rows);
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::default());
+    }
+
+    pub fn clear_row(&mut self, y: u16) {
+        if y < self.rows {
+            let start = y as usize * self.columns as usize;
+            self.cells[start..start + self.columns as usize].fill(Cell::default());
+        }
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    /// Anything falling outside of the screen is cut off, so that a card
+    /// dragged to the edge doesn't wrap around it.
+    pub fn print_at(&mut self, x: u16, y: u16, text: &str) {
+        if y >= self.rows {
+            return;
+        }
+
+        for (x, symbol) in (x..self.columns).zip(text.chars()) {
+            let i = y as usize * self.columns as usize + x as usize;
+            self.cells[i] = Cell {
+                symbol,
+                color: self.color,
+            };
+        }
+    }
+
+    pub fn present(&mut self, backend: &mut impl Backend) -> Result<()> {
+        let shown_cells = self.shown_cells.take();
+        if shown_cells.is_none() {
+            backend.clear()?;
+        }
+
+        let changed_cells: Vec<_> = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|&(i, &cell)| match &shown_cells {
+                Some(shown_cells) => shown_cells[i] != cell,
+                None => cell != Cell::default(),
+            })
+            .map(|(i, &cell)| {
+                let x = (i % self.columns as usize) as u16;
+                let y = (i / self.columns as usize) as u16;
+
+                (x, y, cell)
+            })
+            .collect();
+
+        self.shown_cells = Some(self.cells.clone());
+
+        backend.draw(&changed_cells)
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use super::{card::Card, history::Record, moves::Move, Deal, Game, Rules};
+
+const TIME_CHECK_INTERVAL: usize = 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    pub max_nodes: usize,
+    pub max_time: Duration,
+}
+
+#[derive(Debug)]
+pub enum Solution {
+    Winnable(Vec<Move>),
+    Unwinnable,
+    Unknown,
+}
+
+/// How hard to look for a winnable deal when dealing.
+#[derive(Debug, Clone, Copy)]
+pub struct WinnableDeals {
+    pub attempts: usize,
+    pub budget: Budget,
+}
+
+impl WinnableDeals {
+    /// Deals like `like` until the solver proves a deal winnable. When the
+    /// attempts run out, a deal the solver couldn't decide is preferred to
+    /// one proven unwinnable.
+    pub fn find_deal(&self, rules: Rules, like: Deal) -> Deal {
+        let mut undecided_deal = None;
+        let mut deal = like;
+
+        for _ in 0..self.attempts {
+            deal = like.random_like();
+
+            match solve(&Game::new(rules, deal), self.budget) {
+                Solution::Winnable(_) => return deal,
+                Solution::Unknown => {
+                    undecided_deal.get_or_insert(deal);
+                }
+                Solution::Unwinnable => {}
+            }
+        }
+
+        undecided_deal.unwrap_or(deal)
+    }
+}
+
+struct Frame {
+    record: Option<Record>,
+    moves: Vec<Move>,
+    next_move_i: usize,
+}
+
+/// Depth-first search for a winning line from the position of the game,
+/// hidden cards included. Moves are made with the same code the game uses,
+/// so the solver can't find a line the game wouldn't accept. Positions seen
+/// once are never searched again. Moves that only shuffle cards around are
+/// never tried, so when any was left out, running out of moves only means
+/// no line was found without them and the deal is `Unknown`.
+pub fn solve(game: &Game, budget: Budget) -> Solution {
+    let started_at = Instant::now();
+    let mut game = game.clone();
+    let mut visited = HashSet::new();
+    let mut nodes = 0;
+    let mut is_any_move_left_out = false;
+
+    visited.insert(get_position_key(&game));
+    let mut frames = vec![Frame {
+        record: None,
+        moves: get_ordered_moves(&game, &mut is_any_move_left_out),
+        next_move_i: 0,
+    }];
+
+    while let Some(frame) = frames.last_mut() {
+        if game.is_won() {
+            let moves = frames
+                .iter()
+                .filter_map(|frame| frame.record.map(|record| record.mv))
+                .collect();
+            return Solution::Winnable(moves);
+        }
+
+        let Some(&mv) = frame.moves.get(frame.next_move_i) else {
+            if let Some(record) = frame.record {
+                game.revert_move(record);
+            }
+            frames.pop();
+            continue;
+        };
+        frame.next_move_i += 1;
+
+        nodes += 1;
+        if nodes > budget.max_nodes
+            || (nodes % TIME_CHECK_INTERVAL == 0 && started_at.elapsed() > budget.max_time)
+        {
+            return Solution::Unknown;
+        }
+
+        let Some(record) = game.apply_move(mv) else {
+            continue;
+        };
+
+        if !visited.insert(get_position_key(&game)) {
+            game.revert_move(record);
+            continue;
+        }
+
+        frames.push(Frame {
+            record: Some(record),
+            moves: get_ordered_moves(&game, &mut is_any_move_left_out),
+            next_move_i: 0,
+        });
+    }
+
+    if is_any_move_left_out {
+        Solution::Unknown
+    } else {
+        Solution::Unwinnable
+    }
+}
+
+/// A card that no other card could ever need to be put on goes to its suit
+/// stack straight away, nothing else is tried. Otherwise the moves worth
+/// suggesting as hints are tried, the most promising first, and cards are
+/// taken back from suit stacks only as a last resort. Sending a safe card
+/// up loses nothing, the other moves left out are noted.
+fn get_ordered_moves(game: &Game, is_any_move_left_out: &mut bool) -> Vec<Move> {
+    let moves = game.legal_moves();
+
+    if let Some(&safe_move) = moves.iter().find(|&&mv| is_safe_move(game, mv)) {
+        return vec![safe_move];
+    }
+
+    let legal_moves_count = moves.len();
+    let mut moves: Vec<(i32, Move)> = moves
+        .into_iter()
+        .map(|mv| match mv {
+            Move::SuitStackToStack { .. } => (0, mv),
+            _ => (game.rank_move(mv), mv),
+        })
+        .filter(|&(rank, mv)| rank > 0 || matches!(mv, Move::SuitStackToStack { .. }))
+        .collect();
+    *is_any_move_left_out |= moves.len() < legal_moves_count;
+
+    moves.sort_by_key(|(rank, _)| -rank);
+    moves.into_iter().map(|(_, mv)| mv).collect()
+}
+
+fn is_safe_move(game: &Game, mv: Move) -> bool {
+    let card = match mv {
+        Move::StackToSuitStack { from, .. } => game.stacks[from].last(),
+        Move::PileToSuitStack(_) => game.pile.get_visible_cards().last(),
+        _ => None,
+    };
+
+    let Some(card) = card else {
+        return false;
+    };
+    let value = u8::from(&card.value);
+
+    let covered_suit_stacks_count = game
+        .suit_stacks
+        .iter()
+        .filter_map(|stack| stack.last())
+        .filter(|top| {
+            !top.suit.has_same_color_with(&card.suit) && u8::from(&top.value) + 1 >= value
+        })
+        .count();
+
+    value <= 2 || covered_suit_stacks_count == 2
+}
+
+fn get_position_key(game: &Game) -> Vec<u8> {
+    const SEPARATOR: u8 = u8::MAX;
+
+    let mut stacks: Vec<Vec<u8>> = game
+        .stacks
+        .iter()
+        .map(|stack| stack.get_all().iter().map(get_card_code).collect())
+        .collect();
+    stacks.sort();
+
+    let mut key = Vec::new();
+
+    for stack in stacks {
+        key.extend(stack);
+        key.push(SEPARATOR);
+    }
+    for stack in &game.suit_stacks {
+        key.push(stack.last().map_or(0, get_card_code));
+    }
+    key.push(SEPARATOR);
+    key.extend(game.pile.get_cards().get_all().iter().map(get_card_code));
+    key.push(SEPARATOR);
+    key.extend(game.deck.get_cards().get_all().iter().map(get_card_code));
+    if game.rules.passes_limit.is_some() {
+        key.push(SEPARATOR);
+        key.push(game.passes as u8);
+    }
+
+    key
+}
+
+fn get_card_code(card: &Card) -> u8 {
+    let suit = card.suit.clone() as u8;
+    let code = u8::from(&card.value) * 4 + suit;
+
+    if card.hidden {
+        code | 0x80
+    } else {
+        code
+    }
+}
+
+#[cfg(test)]
+mod tests;
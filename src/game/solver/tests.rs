@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use crate::game::{moves::Move, Budget, Deal, Game, Rules, Solution};
+
+use super::solve;
+
+const BUDGET: Budget = Budget {
+    max_nodes: 5_000_000,
+    max_time: Duration::from_secs(60),
+};
+
+/// Two kings left to send up.
+const NEAR_WON_BOARD: &str = "\
+foundations: Q♣ K♦ K♠ Q♥
+pile:
+deck:
+1: K♣
+2: K♥
+";
+
+/// Every card in one stack, under a two whose ace is buried.
+const BLOCKED_BOARD: &str = "\
+foundations: - - - -
+pile:
+deck:
+1: #A♠ #2♣ #3♣ #4♣ #5♣ #6♣ #7♣ #8♣ #9♣ #10♣ #J♣ #Q♣ #K♣ #A♣ \
+#2♦ #3♦ #4♦ #5♦ #6♦ #7♦ #8♦ #9♦ #10♦ #J♦ #Q♦ #K♦ #A♦ \
+#3♠ #4♠ #5♠ #6♠ #7♠ #8♠ #9♠ #10♠ #J♠ #Q♠ #K♠ \
+#2♥ #3♥ #4♥ #5♥ #6♥ #7♥ #8♥ #9♥ #10♥ #J♥ #Q♥ #K♥ #A♥ 2♠
+";
+
+fn assert_wins(game: &Game, moves: &[Move]) {
+    let mut game = game.clone();
+
+    for &mv in moves {
+        assert!(game.make_move(mv), "{mv} can be made");
+    }
+    assert!(game.is_won());
+}
+
+#[test]
+fn solves_winnable_deal() {
+    let game = Game::new(Rules::default(), Deal::Seeded(5));
+
+    match solve(&game, BUDGET) {
+        Solution::Winnable(moves) => assert_wins(&game, &moves),
+        solution => panic!("Expected a winning line, found {solution:?}"),
+    }
+}
+
+#[test]
+fn solves_near_won_position() {
+    let game = Game::from_board(Rules::default(), NEAR_WON_BOARD).expect("the board is valid");
+
+    match solve(&game, BUDGET) {
+        Solution::Winnable(moves) => {
+            assert_eq!(moves.len(), 2);
+            assert_wins(&game, &moves);
+        }
+        solution => panic!("Expected a winning line, found {solution:?}"),
+    }
+}
+
+#[test]
+fn gives_up_when_budget_runs_out() {
+    let game = Game::new(Rules::default(), Deal::Seeded(5));
+    let budget = Budget {
+        max_nodes: 10,
+        ..BUDGET
+    };
+
+    assert!(matches!(solve(&game, budget), Solution::Unknown));
+}
+
+#[test]
+fn proves_unwinnable_when_no_move_is_left_out() {
+    let game = Game::from_board(Rules::default(), BLOCKED_BOARD).expect("the board is valid");
+
+    assert!(matches!(solve(&game, BUDGET), Solution::Unwinnable));
+}
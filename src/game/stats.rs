@@ -0,0 +1,189 @@
+use std::{
+    collections::BTreeMap,
+    io::{Error, ErrorKind, Result},
+    time::Duration,
+};
+
+use crate::storage;
+
+use super::{Game, Rules, ScoringMode};
+
+const STATS_FILE: &str = "stats";
+
+#[derive(Debug, Default, Clone)]
+pub struct RuleSetStats {
+    pub played: u32,
+    pub won: u32,
+    pub best_time: Option<Duration>,
+    pub fewest_moves: Option<u32>,
+    pub best_score: Option<i32>,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+}
+
+/// Results of every game played, kept apart for every draw count and
+/// scoring mode. Stored as one line per rule set, its name followed by
+/// `name=value` fields.
+#[derive(Debug, Default)]
+pub struct Stats {
+    rule_sets: BTreeMap<String, RuleSetStats>,
+    is_read_only: bool,
+}
+
+impl RuleSetStats {
+    pub fn win_rate(&self) -> u32 {
+        match self.played {
+            0 => 0,
+            played => self.won * 100 / played,
+        }
+    }
+
+    fn add_win(&mut self, game: &Game) {
+        self.won += 1;
+        self.current_streak += 1;
+        self.longest_streak = self.longest_streak.max(self.current_streak);
+
+        let elapsed = Duration::from_secs(game.elapsed().as_secs());
+        self.best_time = Some(self.best_time.map_or(elapsed, |best| best.min(elapsed)));
+        let moves = game.moves_count();
+        self.fewest_moves = Some(self.fewest_moves.map_or(moves, |best| best.min(moves)));
+        let score = game.score();
+        self.best_score = Some(self.best_score.map_or(score, |best| best.max(score)));
+    }
+
+    fn to_line(&self, name: &str) -> String {
+        let mut fields = vec![
+            format!("played={}", self.played),
+            format!("won={}", self.won),
+            format!("current-streak={}", self.current_streak),
+            format!("longest-streak={}", self.longest_streak),
+        ];
+
+        if let Some(best_time) = self.best_time {
+            fields.push(format!("best-time={}", best_time.as_secs()));
+        }
+        if let Some(fewest_moves) = self.fewest_moves {
+            fields.push(format!("fewest-moves={fewest_moves}"));
+        }
+        if let Some(best_score) = self.best_score {
+            fields.push(format!("best-score={best_score}"));
+        }
+
+        format!("{name} {}", fields.join(" "))
+    }
+
+    fn from_fields<'a>(fields: impl Iterator<Item = &'a str>) -> Option<Self> {
+        let mut stats = Self::default();
+
+        for field in fields {
+            let (name, value) = field.split_once('=')?;
+            match name {
+                "played" => stats.played = value.parse().ok()?,
+                "won" => stats.won = value.parse().ok()?,
+                "current-streak" => stats.current_streak = value.parse().ok()?,
+                "longest-streak" => stats.longest_streak = value.parse().ok()?,
+                "best-time" => stats.best_time = Some(Duration::from_secs(value.parse().ok()?)),
+                "fewest-moves" => stats.fewest_moves = Some(value.parse().ok()?),
+                "best-score" => stats.best_score = Some(value.parse().ok()?),
+                _ => return None,
+            }
+        }
+
+        Some(stats)
+    }
+}
+
+impl Stats {
+    pub fn load() -> Result<Self> {
+        let Some(contents) = storage::read(STATS_FILE)? else {
+            return Ok(Self::default());
+        };
+
+        Self::from_text(&contents).map_err(|error| Error::new(ErrorKind::InvalidData, error))
+    }
+
+    /// Stats to play with when the file couldn't be loaded. Nothing is saved,
+    /// so that the file is still there to be fixed instead of being written
+    /// over with the games of one session.
+    pub fn read_only() -> Self {
+        Self {
+            is_read_only: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn get(&self, rules: &Rules) -> RuleSetStats {
+        self.rule_sets
+            .get(&get_rule_set_name(rules))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// A game already counted as lost, because it was left and then
+    /// resumed, is turned into a win.
+    pub fn record_win(&mut self, game: &Game) -> Result<()> {
+        let stats = self.get_mut(&game.rules);
+        if !game.is_counted_in_stats {
+            stats.played += 1;
+        }
+        stats.add_win(game);
+
+        self.save()
+    }
+
+    pub fn record_loss(&mut self, game: &Game) -> Result<()> {
+        let stats = self.get_mut(&game.rules);
+        stats.played += 1;
+        stats.current_streak = 0;
+
+        self.save()
+    }
+
+    fn get_mut(&mut self, rules: &Rules) -> &mut RuleSetStats {
+        self.rule_sets.entry(get_rule_set_name(rules)).or_default()
+    }
+
+    fn from_text(text: &str) -> std::result::Result<Self, String> {
+        let mut rule_sets = BTreeMap::new();
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let invalid_line = || format!("Invalid stats line '{line}'");
+            let mut fields = line.split_whitespace();
+            let name = fields.next().ok_or_else(invalid_line)?;
+            let stats = RuleSetStats::from_fields(fields).ok_or_else(invalid_line)?;
+            rule_sets.insert(name.to_string(), stats);
+        }
+
+        Ok(Self {
+            rule_sets,
+            is_read_only: false,
+        })
+    }
+
+    fn to_text(&self) -> String {
+        self.rule_sets
+            .iter()
+            .map(|(name, stats)| stats.to_line(name) + "\n")
+            .collect()
+    }
+
+    fn save(&self) -> Result<()> {
+        if self.is_read_only {
+            return Ok(());
+        }
+
+        storage::write(STATS_FILE, &self.to_text())
+    }
+}
+
+fn get_rule_set_name(rules: &Rules) -> String {
+    let scoring_mode = match rules.scoring_mode {
+        ScoringMode::Standard => "standard",
+        ScoringMode::Vegas => "vegas",
+    };
+
+    format!("draw-{}-{scoring_mode}", rules.draw_count)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use super::Stats;
+
+const STATS_TEXT: &str = "\
+draw-1-standard played=4 won=1 current-streak=0 longest-streak=1 best-time=300 fewest-moves=120 best-score=650
+draw-3-vegas played=2 won=0 current-streak=0 longest-streak=0
+";
+
+#[test]
+fn reads_stats_written_by_save() {
+    let stats = Stats::from_text(STATS_TEXT).expect("the stats are valid");
+
+    let draw_one = &stats.rule_sets["draw-1-standard"];
+    assert_eq!((draw_one.played, draw_one.won), (4, 1));
+    assert_eq!(draw_one.best_time, Some(Duration::from_secs(300)));
+    assert_eq!(draw_one.best_score, Some(650));
+    assert_eq!(stats.rule_sets["draw-3-vegas"].fewest_moves, None);
+    assert_eq!(stats.to_text(), STATS_TEXT);
+}
+
+/// A single line that can't be read fails the whole file, which is then
+/// left alone for the player to fix.
+#[test]
+fn rejects_stats_with_one_bad_line() {
+    let text = format!("{STATS_TEXT}draw-3-standard played=lots won=0\n");
+
+    assert_eq!(
+        Stats::from_text(&text).err(),
+        Some("Invalid stats line 'draw-3-standard played=lots won=0'".to_string())
+    );
+}
@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct Stopwatch {
+    started_at: Option<Instant>,
+    elapsed: Duration,
+}
+
+impl Stopwatch {
+    pub fn started() -> Self {
+        Self {
+            started_at: Some(Instant::now()),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn resumed(elapsed: Duration) -> Self {
+        Self {
+            started_at: Some(Instant::now()),
+            elapsed,
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(started_at) = self.started_at.take() {
+            self.elapsed += started_at.elapsed();
+        }
+    }
+
+    pub fn restart(&mut self) {
+        *self = Self::started();
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        match self.started_at {
+            Some(started_at) => self.elapsed + started_at.elapsed(),
+            None => self.elapsed,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    io::{stdout, Result},
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+};
+
+use crossterm::{
+    cursor::{Hide, Show},
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+static IS_ACTIVE: AtomicBool = AtomicBool::new(false);
+static PANIC_HOOK: Once = Once::new();
+
+/// Puts the terminal in the state the game is drawn in: raw mode, the
+/// alternate screen, a hidden cursor and mouse capture. Everything is put
+/// back when the session is dropped, which also happens when the game stops
+/// on an error. A panic puts it back before its message is printed, so that
+/// the message isn't lost on the alternate screen.
+pub struct TerminalSession;
+
+impl TerminalSession {
+    pub fn start() -> Result<Self> {
+        PANIC_HOOK.call_once(|| {
+            let default_hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                restore();
+                default_hook(info);
+            }));
+        });
+
+        enable_raw_mode()?;
+        IS_ACTIVE.store(true, Ordering::SeqCst);
+        // Made before the rest is set up, so that raw mode is turned off
+        // again if any of it fails.
+        let session = Self;
+        execute!(stdout(), EnterAlternateScreen, Hide, EnableMouseCapture)?;
+
+        Ok(session)
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Errors are ignored, there is nothing left to do about them while the
+/// terminal is being given back.
+fn restore() {
+    if !IS_ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    let _ = execute!(stdout(), DisableMouseCapture, Show, LeaveAlternateScreen);
+    let _ = disable_raw_mode();
+}
@@ -0,0 +1,151 @@
+use std::str::FromStr;
+
+use crossterm::style::Color;
+
+use super::card::Suit;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Theme {
+    #[default]
+    Classic,
+    Dark,
+    HighContrast,
+    FourColor,
+    Monochrome,
+}
+
+/// The colors everything on the table is drawn in.
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    pub card: Color,
+    pub card_back: Color,
+    pub placeholder: Color,
+    pub text: Color,
+    pub status_bar: Color,
+    pub cursor: Color,
+    pub hint: Color,
+    clubs: Color,
+    diamonds: Color,
+    spades: Color,
+    hearts: Color,
+}
+
+impl Theme {
+    /// The four-color deck uses the Okabe-Ito colors, which stay apart for
+    /// the common kinds of color blindness.
+    pub fn palette(self) -> Palette {
+        match self {
+            Self::Classic => Palette {
+                card: Color::White,
+                card_back: Color::White,
+                placeholder: Color::DarkGrey,
+                text: Color::White,
+                status_bar: Color::DarkGrey,
+                cursor: Color::Yellow,
+                hint: Color::Green,
+                clubs: Color::White,
+                diamonds: Color::Red,
+                spades: Color::White,
+                hearts: Color::Red,
+            },
+            Self::Dark => Palette {
+                card: Color::Grey,
+                card_back: Color::DarkBlue,
+                placeholder: Color::DarkGrey,
+                text: Color::Grey,
+                status_bar: Color::DarkGrey,
+                cursor: Color::DarkYellow,
+                hint: Color::DarkGreen,
+                clubs: Color::Grey,
+                diamonds: Color::DarkRed,
+                spades: Color::Grey,
+                hearts: Color::DarkRed,
+            },
+            Self::HighContrast => Palette {
+                card: Color::White,
+                card_back: Color::White,
+                placeholder: Color::Grey,
+                text: Color::White,
+                status_bar: Color::White,
+                cursor: Color::Yellow,
+                hint: Color::Cyan,
+                clubs: Color::White,
+                diamonds: Color::Red,
+                spades: Color::White,
+                hearts: Color::Red,
+            },
+            Self::FourColor => Palette {
+                card: Color::White,
+                card_back: Color::White,
+                placeholder: Color::DarkGrey,
+                text: Color::White,
+                status_bar: Color::DarkGrey,
+                cursor: Color::Rgb {
+                    r: 240,
+                    g: 228,
+                    b: 66,
+                },
+                hint: Color::Rgb {
+                    r: 204,
+                    g: 121,
+                    b: 167,
+                },
+                clubs: Color::Rgb {
+                    r: 0,
+                    g: 158,
+                    b: 115,
+                },
+                diamonds: Color::Rgb {
+                    r: 0,
+                    g: 114,
+                    b: 178,
+                },
+                spades: Color::White,
+                hearts: Color::Rgb {
+                    r: 213,
+                    g: 94,
+                    b: 0,
+                },
+            },
+            Self::Monochrome => Palette {
+                card: Color::Grey,
+                card_back: Color::DarkGrey,
+                placeholder: Color::DarkGrey,
+                text: Color::Grey,
+                status_bar: Color::DarkGrey,
+                cursor: Color::White,
+                hint: Color::White,
+                clubs: Color::Grey,
+                diamonds: Color::Grey,
+                spades: Color::Grey,
+                hearts: Color::Grey,
+            },
+        }
+    }
+}
+
+impl Palette {
+    pub fn get_suit_color(&self, suit: &Suit) -> Color {
+        match suit {
+            Suit::Clubs => self.clubs,
+            Suit::Diamonds => self.diamonds,
+            Suit::Spades => self.spades,
+            Suit::Hearts => self.hearts,
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "classic" => Ok(Self::Classic),
+            "dark" => Ok(Self::Dark),
+            "high-contrast" => Ok(Self::HighContrast),
+            "four-color" => Ok(Self::FourColor),
+            "monochrome" => Ok(Self::Monochrome),
+            _ => Err(format!("Invalid theme '{text}'")),
+        }
+    }
+}
@@ -1,8 +1,174 @@
+mod config;
 mod game;
+mod storage;
 
-use game::Game;
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    process,
+    time::Duration,
+};
+
+use config::{Config, USAGE};
+use game::{Bankroll, Budget, Deal, Game, GameRecord, Rules, ScoringMode, Solution, WinnableDeals};
+
+const SOLVER_BUDGET: Budget = Budget {
+    max_nodes: 5_000_000,
+    max_time: Duration::from_secs(60),
+};
 
 fn main() {
-    let mut game = Game::default();
-    game.start();
+    let config = match Config::load(env::args().skip(1)) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    if config.help {
+        println!("{USAGE}");
+        return;
+    }
+
+    if let Some(path) = &config.replay {
+        match GameRecord::load(path) {
+            Ok(record) => record.replay(config.theme),
+            Err(error) => {
+                eprintln!("Can't load the record from '{path}': {error}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let rules = config.rules();
+    let winnable_deals = config.winnable_deals();
+
+    let resumed_game = match (config.seed, config.deal, &config.board) {
+        (None, None, None) if !config.solve && !config.print_board => ask_to_resume(&config),
+        _ => None,
+    };
+
+    let mut game = match (resumed_game, &config.board) {
+        (Some(game), _) => game,
+        (None, Some(path)) => load_board(rules, path),
+        (None, None) => Game::new(rules, get_deal(&config, winnable_deals)),
+    };
+    if let Some(winnable_deals) = winnable_deals {
+        game = game.with_winnable_deals(winnable_deals);
+    }
+
+    if config.solve {
+        print_solution(&game);
+        return;
+    }
+
+    if config.print_board {
+        print!("{game}");
+        return;
+    }
+
+    if config.cumulative && game.rules().scoring_mode == ScoringMode::Vegas {
+        match Bankroll::load() {
+            Ok(bankroll) => game = game.with_bankroll(bankroll),
+            Err(error) => {
+                eprintln!("Error loading the bankroll: {error}");
+                process::exit(1);
+            }
+        }
+    }
+
+    game.start(config.theme);
+}
+
+fn get_deal(config: &Config, winnable_deals: Option<WinnableDeals>) -> Deal {
+    match (config.seed, config.deal, winnable_deals) {
+        (Some(seed), _, _) => Deal::Seeded(seed),
+        (_, Some(number), _) => Deal::Microsoft(number),
+        (_, _, Some(winnable_deals)) => {
+            println!("Looking for a winnable deal...");
+            winnable_deals.find_deal(config.rules(), Deal::random())
+        }
+        _ => Deal::random(),
+    }
+}
+
+fn load_board(rules: Rules, path: &str) -> Game {
+    let result = fs::read_to_string(path)
+        .map_err(|error| error.to_string())
+        .and_then(|text| Game::from_board(rules, &text));
+
+    match result {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("Can't load the board from '{path}': {error}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Offers the game left unfinished last time. Any answer but "n" resumes
+/// it. A game played by other rules than the ones given on the command line
+/// can't be resumed with them, it's given up for a new game only when the
+/// player says so and kept for later otherwise.
+fn ask_to_resume(config: &Config) -> Option<Game> {
+    if !io::stdin().is_terminal() {
+        return None;
+    }
+
+    let game = match Game::load_saved() {
+        Ok(game) => game?,
+        Err(error) => {
+            eprintln!("Can't resume the saved game: {error}");
+            return None;
+        }
+    };
+
+    if config.has_rule_options && game.rules() != config.rules() {
+        println!(
+            "The unfinished game ({}, {} moves) is played by other rules. Give it up? [y/N]",
+            game.deal_text(),
+            game.moves_count()
+        );
+
+        if !matches!(read_answer().as_deref(), Some("y" | "Y")) {
+            println!("Keeping it, start without rule options to resume it");
+            process::exit(0);
+        }
+        if let Err(error) = game.give_up_saved() {
+            eprintln!("Error giving up the unfinished game: {error}");
+        }
+        return None;
+    }
+
+    println!(
+        "Resume the unfinished game ({}, {} moves)? [Y/n]",
+        game.deal_text(),
+        game.moves_count()
+    );
+
+    match read_answer()?.as_str() {
+        "n" | "N" => None,
+        _ => Some(game),
+    }
+}
+
+fn read_answer() -> Option<String> {
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).ok()?;
+
+    Some(answer.trim().to_string())
+}
+
+fn print_solution(game: &Game) {
+    match game.solve(SOLVER_BUDGET) {
+        Solution::Winnable(moves) => {
+            let moves: Vec<String> = moves.iter().map(ToString::to_string).collect();
+            println!("{}: winnable in {} moves", game.deal_text(), moves.len());
+            println!("{}", moves.join(" "));
+        }
+        Solution::Unwinnable => println!("{}: unwinnable", game.deal_text()),
+        Solution::Unknown => println!("{}: no solution found within the budget", game.deal_text()),
+    }
 }
@@ -0,0 +1,40 @@
+use std::{
+    env, fs,
+    io::{Error, ErrorKind, Result},
+    path::PathBuf,
+};
+
+const DATA_DIR_NAME: &str = ".consolitaire";
+
+pub fn data_dir() -> Result<PathBuf> {
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .ok_or_else(|| Error::new(ErrorKind::NotFound, "home directory is not set"))?;
+
+    Ok(PathBuf::from(home).join(DATA_DIR_NAME))
+}
+
+pub fn read(name: &str) -> Result<Option<String>> {
+    match fs::read_to_string(data_dir()?.join(name)) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn remove(name: &str) -> Result<()> {
+    match fs::remove_file(data_dir()?.join(name)) {
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// `name` can include subdirectories of the data directory, they are
+/// created as needed.
+pub fn write(name: &str, contents: &str) -> Result<()> {
+    let path = data_dir()?.join(name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}